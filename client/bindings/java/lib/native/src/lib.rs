@@ -10,11 +10,11 @@ use jni::{
     JNIEnv,
 };
 use lazy_static::lazy_static;
-use once_cell::sync::OnceCell;
 use tokio::runtime::Runtime;
 
 lazy_static! {
     static ref MESSAGE_HANDLER: Mutex<Option<ClientMessageHandler>> = Mutex::new(None);
+    static ref RUNTIME: Mutex<Option<Runtime>> = Mutex::new(None);
 }
 
 #[no_mangle]
@@ -30,34 +30,13 @@ pub extern "system" fn Java_org_iota_apis_NativeApi_createMessageHandler(
     let config: String = match env.get_string(&config) {
         Ok(jstring) => jstring.into(),
         Err(err) => {
-            env.throw_new("java/lang/Exception", err.to_string()).unwrap();
+            throw_exception(&mut env, err.to_string());
             return;
         }
     };
 
-    match MESSAGE_HANDLER.lock() {
-        Ok(mut message_handler_store) => {
-            // throw an exception if a message handler already exists
-            if message_handler_store.is_some() {
-                env.throw_new("java/lang/Exception", "a client instance was already created")
-                    .unwrap();
-                return;
-            }
-
-            match iota_client::message_interface::create_message_handler(Some(config)) {
-                Ok(message_handler) => {
-                    message_handler_store.replace(message_handler);
-                }
-                Err(err) => {
-                    env.throw_new("java/lang/Exception", err.to_string()).unwrap();
-                    // no return needed because no code has to be executed after
-                }
-            }
-        }
-        Err(err) => {
-            env.throw_new("java/lang/Exception", err.to_string()).unwrap();
-            // no return needed because no code has to be executed after
-        }
+    if let Err(err) = create_message_handler(config) {
+        throw_exception(&mut env, err);
     }
 }
 
@@ -72,33 +51,123 @@ pub extern "system" fn Java_org_iota_apis_NativeApi_sendCommand(
     _class: JClass,
     command: JString,
 ) -> jstring {
-    if env.exception_check().unwrap() {
+    // A pending exception has to reach Java first, also if it can't be checked.
+    if env.exception_check().unwrap_or(true) {
         return std::ptr::null_mut();
     }
 
-    let command: String = env.get_string(&command).expect("Couldn't get java string!").into();
-
-    let message = serde_json::from_str::<Message>(&command).unwrap();
-
-    let guard = MESSAGE_HANDLER.lock().unwrap();
-
-    let response = block_on(guard.as_ref().unwrap().send_message(message));
+    let command: String = match env.get_string(&command) {
+        Ok(jstring) => jstring.into(),
+        Err(err) => {
+            throw_exception(&mut env, format!("couldn't get the command string: {err}"));
+            return std::ptr::null_mut();
+        }
+    };
 
-    let output = env
-        .new_string(serde_json::to_string(&response).unwrap())
-        .expect("Couldn't create java string!");
+    let response = match send_command(&command) {
+        Ok(response) => response,
+        Err(err) => {
+            throw_exception(&mut env, err);
+            return std::ptr::null_mut();
+        }
+    };
 
-    output.into_raw()
+    match env.new_string(response) {
+        Ok(output) => output.into_raw(),
+        Err(err) => {
+            throw_exception(&mut env, format!("couldn't create the response string: {err}"));
+            std::ptr::null_mut()
+        }
+    }
 }
 
 // Destroy the required parts for messaging. Needs to call createMessageHandler again before resuming
 #[no_mangle]
 pub extern "system" fn Java_org_iota_apis_NativeApi_destroyHandle(_env: JNIEnv, _class: JClass) {
-    (*MESSAGE_HANDLER.lock().unwrap()) = None;
+    destroy_message_handler();
+}
+
+fn create_message_handler(config: String) -> Result<(), String> {
+    let mut message_handler_store = MESSAGE_HANDLER.lock().map_err(|err| err.to_string())?;
+    // return an error if a message handler already exists
+    if message_handler_store.is_some() {
+        return Err("a client instance was already created".to_string());
+    }
+
+    let message_handler =
+        iota_client::message_interface::create_message_handler(Some(config)).map_err(|err| err.to_string())?;
+    message_handler_store.replace(message_handler);
+
+    Ok(())
 }
 
-pub(crate) fn block_on<C: futures::Future>(cb: C) -> C::Output {
-    static INSTANCE: OnceCell<Mutex<Runtime>> = OnceCell::new();
-    let runtime = INSTANCE.get_or_init(|| Mutex::new(Runtime::new().unwrap()));
-    runtime.lock().unwrap().block_on(cb)
+fn send_command(command: &str) -> Result<String, String> {
+    let message = serde_json::from_str::<Message>(command).map_err(|err| format!("invalid command: {err}"))?;
+
+    let guard = MESSAGE_HANDLER.lock().map_err(|err| err.to_string())?;
+    let message_handler = guard.as_ref().ok_or("message handler not initialized")?;
+
+    let response = block_on(message_handler.send_message(message))?;
+
+    serde_json::to_string(&response).map_err(|err| format!("couldn't serialize the response: {err}"))
+}
+
+fn destroy_message_handler() {
+    // Holding the lock waits for a running command to finish before the client is dropped.
+    let mut message_handler_store = MESSAGE_HANDLER.lock().unwrap_or_else(|err| err.into_inner());
+    message_handler_store.take();
+    // The runtime is only used to run commands, so it can be shut down together with the message handler.
+    if let Some(runtime) = RUNTIME.lock().unwrap_or_else(|err| err.into_inner()).take() {
+        runtime.shutdown_background();
+    }
+}
+
+fn throw_exception(env: &mut JNIEnv, message: impl AsRef<str>) {
+    // Nothing else can be reported to Java if even throwing fails.
+    let _ = env.throw_new("java/lang/Exception", message);
+}
+
+pub(crate) fn block_on<C: futures::Future>(cb: C) -> Result<C::Output, String> {
+    let mut runtime = RUNTIME.lock().map_err(|err| err.to_string())?;
+    let runtime = match runtime.as_mut() {
+        Some(runtime) => runtime,
+        None => runtime.insert(Runtime::new().map_err(|err| format!("couldn't create the runtime: {err}"))?),
+    };
+    Ok(runtime.block_on(cb))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_destroy_create() {
+        let config = r#"{"nodes":[],"ignoreNodeHealth":true}"#;
+        let command = r#"{"name":"generateMnemonic"}"#;
+
+        create_message_handler(config.to_string()).unwrap();
+        assert!(create_message_handler(config.to_string()).is_err());
+        assert!(send_command(command).unwrap().contains("generatedMnemonic"));
+
+        destroy_message_handler();
+        assert!(MESSAGE_HANDLER.lock().unwrap().is_none());
+        assert!(RUNTIME.lock().unwrap().is_none());
+        assert_eq!(send_command(command).unwrap_err(), "message handler not initialized");
+
+        create_message_handler(config.to_string()).unwrap();
+        assert!(send_command(command).unwrap().contains("generatedMnemonic"));
+        destroy_message_handler();
+    }
+
+    #[test]
+    fn invalid_command() {
+        for command in [
+            "",
+            "{",
+            r#"{"name":"unknownMessage"}"#,
+            r#"{"name":"mnemonicToHexSeed"}"#,
+        ] {
+            assert!(send_command(command).unwrap_err().starts_with("invalid command: "));
+        }
+    }
 }
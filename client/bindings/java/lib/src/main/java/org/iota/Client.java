@@ -610,6 +610,17 @@ public class Client extends NativeApi {
         return utilsApi.generateMnemonic();
     }
 
+    /**
+     * Generate a mnemonic with the given number of words
+     *
+     * @param wordCount The number of words, has to be 12, 15, 18, 21 or 24.
+     * @return The mnemonic string.
+     * @throws ClientException on error.
+     */
+    public String generateMnemonic(int wordCount) throws ClientException {
+        return utilsApi.generateMnemonic(wordCount);
+    }
+
     /**
      * Converts a mnemonic to a hex seed
      *
@@ -89,6 +89,14 @@ public class UtilsApi {
         return responsePayload;
     }
 
+    public String generateMnemonic(int wordCount) throws ClientException {
+        JsonObject o = new JsonObject();
+        o.addProperty("wordCount", wordCount);
+
+        String responsePayload = nativeApi.sendCommand(new ClientCommand("generateMnemonicWithWordCount", o)).getAsString();
+        return responsePayload;
+    }
+
     public String mnemonicToHexSeed(String mnemonic) throws ClientException {
         JsonObject o = new JsonObject();
         o.addProperty("mnemonic", mnemonic);
@@ -21,6 +21,7 @@ import type {
     AliasQueryParameter,
     LedgerNanoStatus,
     IInputSigningData,
+    ISegment,
     OutputIdsResponse,
 } from '../types';
 import type {
@@ -43,6 +44,8 @@ import type {
     INodeInfoProtocol,
     UnlockTypes,
     ITransactionEssence,
+    IEd25519Signature,
+    HexEncodedString,
 } from '@iota/types';
 import type { INodeInfoWrapper } from '../types/nodeInfo';
 
@@ -116,11 +119,14 @@ export class Client {
     }
 
     /**
-     * Generates a new mnemonic.
+     * Generates a new mnemonic with the given number of words (12, 15, 18, 21 or 24), 24 by default.
      */
-    async generateMnemonic(): Promise<string> {
+    async generateMnemonic(wordCount = 24): Promise<string> {
         const response = await this.messageHandler.sendMessage({
-            name: 'generateMnemonic',
+            name: 'generateMnemonicWithWordCount',
+            data: {
+                wordCount,
+            },
         });
 
         return JSON.parse(response).payload;
@@ -402,6 +408,46 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Sign a hex encoded message with the Ed25519 key derived at the given chain of the `secretManager`.
+     */
+    async signEd25519(
+        secretManager: SecretManager,
+        message: HexEncodedString,
+        chain: ISegment[],
+    ): Promise<IEd25519Signature> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'signEd25519',
+            data: {
+                secretManager,
+                message,
+                chain,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
+    /**
+     * Verify an Ed25519 signature of a hex encoded message.
+     */
+    async verifyEd25519Signature(
+        publicKey: HexEncodedString,
+        signature: HexEncodedString,
+        message: HexEncodedString,
+    ): Promise<boolean> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'verifyEd25519Signature',
+            data: {
+                publicKey,
+                signature,
+                message,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Submit a payload in a block
      */
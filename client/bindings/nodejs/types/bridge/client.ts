@@ -1,4 +1,9 @@
-import type { IBlock, ITransactionEssence, PayloadTypes } from '@iota/types';
+import type {
+    HexEncodedString,
+    IBlock,
+    ITransactionEssence,
+    PayloadTypes,
+} from '@iota/types';
 import type { SecretManager } from '../secretManager';
 import type { IGenerateAddressesOptions } from '../generateAddressesOptions';
 import type { IBuildBlockOptions } from '../buildBlockOptions';
@@ -6,6 +11,7 @@ import type { BlockId } from '../blockId';
 import type {
     IInputSigningData,
     IPreparedTransactionData,
+    ISegment,
 } from '../preparedTransactionData';
 import type {
     AliasQueryParameter,
@@ -48,6 +54,13 @@ export interface __GenerateMnemonicMessage__ {
     name: 'generateMnemonic';
 }
 
+export interface __GenerateMnemonicWithWordCountMessage__ {
+    name: 'generateMnemonicWithWordCount';
+    data: {
+        wordCount: number;
+    };
+}
+
 export interface __MnemonicToHexSeedMessage__ {
     name: 'mnemonicToHexSeed';
     data: {
@@ -173,6 +186,24 @@ export interface __SignatureUnlockMessage__ {
     };
 }
 
+export interface __SignEd25519Message__ {
+    name: 'signEd25519';
+    data: {
+        secretManager: SecretManager;
+        message: HexEncodedString;
+        chain: ISegment[];
+    };
+}
+
+export interface __VerifyEd25519SignatureMessage__ {
+    name: 'verifyEd25519Signature';
+    data: {
+        publicKey: HexEncodedString;
+        signature: HexEncodedString;
+        message: HexEncodedString;
+    };
+}
+
 export interface __StoreMnemonicMessage__ {
     name: 'storeMnemonic';
     data: {
@@ -4,6 +4,7 @@ import type {
     __GetOutputMessage__,
     __GetOutputsMessage__,
     __GenerateMnemonicMessage__,
+    __GenerateMnemonicWithWordCountMessage__,
     __MnemonicToHexSeedMessage__,
     __ComputeAliasIdMessage__,
     __ComputeNftIdMessage__,
@@ -75,6 +76,8 @@ import type {
     __HashTransactionEssenceMessage__,
     __ClearListenersMessage__,
     __SignatureUnlockMessage__,
+    __SignEd25519Message__,
+    __VerifyEd25519SignatureMessage__,
 } from './client';
 
 export type __ClientMessages__ =
@@ -83,6 +86,7 @@ export type __ClientMessages__ =
     | __GetBasicOutputIdsMessage__
     | __GetOutputsMessage__
     | __GenerateMnemonicMessage__
+    | __GenerateMnemonicWithWordCountMessage__
     | __MnemonicToHexSeedMessage__
     | __ComputeAliasIdMessage__
     | __ComputeNftIdMessage__
@@ -100,6 +104,8 @@ export type __ClientMessages__ =
     | __PrepareTransactionMessage__
     | __SignTransactionMessage__
     | __SignatureUnlockMessage__
+    | __SignEd25519Message__
+    | __VerifyEd25519SignatureMessage__
     | __StoreMnemonicMessage__
     | __PostBlockPayloadMessage__
     | __ParseBech32AddressMessage__
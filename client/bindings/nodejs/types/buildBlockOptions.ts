@@ -17,12 +17,16 @@ export interface IBuildBlockOptions {
     output?: IClientBlockBuilderOutputAddress;
     /** Hex encoded output address and amount */
     outputHex?: IClientBlockBuilderOutputAddress;
+    /** Hex encoded tag for the outputs created from `output` and `outputHex` */
+    outputTag?: string;
     outputs?: OutputTypes[];
     customRemainderAddress?: string;
     tag?: string;
     data?: string;
     /** Parent block IDs */
     parents?: string[];
+    /** Maximum amount of tips used as parents, if no parents are provided */
+    maxParents?: number;
     /** Explicit burning of aliases, nfts, foundries and native tokens */
     burn?: Burn;
 }
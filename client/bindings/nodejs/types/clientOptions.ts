@@ -12,8 +12,12 @@ export interface IClientOptions {
     permanodes?: Array<string | INode>;
     /** If the node health status should be ignored */
     ignoreNodeHealth?: boolean;
+    /** If the nodes shouldn't be synced, every node is then considered synced and ready to use */
+    nodeSyncDisabled?: boolean;
     /** Interval in which nodes will be checked for their sync status and the NetworkInfo gets updated */
     nodeSyncInterval?: IDuration;
+    /** Maximum amount of milestones a node can lag behind the most recent node of the network to still be used */
+    maxMilestoneLag?: number;
     /** If node quorum is enabled. Will compare the responses from multiple nodes and only returns the
      * response if quorum_threshold of the nodes return the same one
      */
@@ -22,6 +26,10 @@ export interface IClientOptions {
     minQuorumSize?: number;
     /** % of nodes that have to return the same response so it gets accepted */
     quorumThreshold?: number;
+    /** Maximum amount of idle connections kept open per node, unlimited by default */
+    poolMaxIdlePerHost?: number;
+    /** Time after which idle connections get closed, 90 seconds by default */
+    poolIdleTimeout?: IDuration;
     /** Data related to the used network */
     networkInfo?: INetworkInfo;
     /** Options for the MQTT broker */
@@ -34,6 +42,22 @@ export interface IClientOptions {
     powWorkerCount?: number;
     /** Whether the PoW should be done locally or remotely. */
     localPow?: boolean;
+    /** Tolerated difference between the local time and the time of the node when evaluating timelocks and expirations */
+    timeSkewTolerance?: IDuration;
+    /** Tolerated difference between the latest milestone timestamps of the nodes when checking the local time */
+    nodeClockSkewTolerance?: IDuration;
+    /** Maximum amount of inputs a transaction can have before consolidation is required, 128 by default */
+    inputConsolidationThreshold?: number;
+    /** Options for caching requested outputs, no outputs are cached if not set */
+    outputCache?: IOutputCacheOptions;
+}
+
+/** Options for the output cache */
+export interface IOutputCacheOptions {
+    /** Maximum amount of cached outputs, the least recently used one is removed when it's exceeded */
+    capacity: number;
+    /** Time after which a cached unspent output is requested again, spent outputs don't expire */
+    ttl: IDuration;
 }
 
 /** Time duration */
@@ -12,11 +12,12 @@ export enum Network {
 }
 
 /**
- * Basic Auth or JWT.
+ * Basic Auth or JWT, and additional headers.
  */
 export interface IAuth {
     jwt?: string;
     basicAuthNamePwd?: [string, string];
+    headers?: { [name: string]: string };
 }
 
 /**
@@ -53,6 +53,8 @@ export type NftQueryParameter =
 
 /** Shared query parameters*/
 type CommonQueryParameters =
+    | AmountGreaterThan
+    | AmountLessThan
     | HasNativeTokens
     | MinNativeTokenCount
     | MaxNativeTokenCount
@@ -96,6 +98,14 @@ interface TimelockedAfter {
 interface HasExpiration {
     hasExpiration: boolean;
 }
+/** Returns outputs with an amount greater than the given one. */
+interface AmountGreaterThan {
+    amountGreaterThan: number;
+}
+/** Returns outputs with an amount less than the given one. */
+interface AmountLessThan {
+    amountLessThan: number;
+}
 /** Filters outputs based on the presence of native tokens. */
 interface HasNativeTokens {
     hasNativeTokens: boolean;
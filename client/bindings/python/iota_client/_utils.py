@@ -56,10 +56,12 @@ class Utils(BaseAPI):
             'address': address
         })
 
-    def generate_mnemonic(self):
-        """Generates a new mnemonic.
+    def generate_mnemonic(self, word_count=24):
+        """Generates a new mnemonic with the given number of words (12, 15, 18, 21 or 24).
         """
-        return self.send_message('generateMnemonic')
+        return self.send_message('generateMnemonicWithWordCount', {
+            'wordCount': word_count
+        })
 
     def mnemonic_to_hex_seed(self, mnemonic):
         """Returns a hex encoded seed for a mnemonic.
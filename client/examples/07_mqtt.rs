@@ -47,9 +47,12 @@ async fn main() -> Result<()> {
                 println!("Topic: {}", event.topic);
                 match &event.payload {
                     MqttPayload::Json(val) => println!("{}", serde_json::to_string(&val).unwrap()),
+                    MqttPayload::BlockMetadata(metadata) => println!("{metadata:?}"),
+                    MqttPayload::Output(output) => println!("{output:?}"),
                     MqttPayload::Block(block) => println!("{block:?}"),
                     MqttPayload::MilestonePayload(ms) => println!("{ms:?}"),
                     MqttPayload::Receipt(receipt) => println!("{receipt:?}"),
+                    MqttPayload::Disconnected => println!("Disconnected from the broker"),
                 }
                 tx.lock().unwrap().send(()).unwrap();
             },
@@ -24,21 +24,14 @@ async fn main() -> Result<()> {
 
     println!("{block:#?}");
 
-    // Try to check if the block has been confirmed.
-    let _ = client.retry_until_included(&block_id, None, None).await?;
-
-    // Get the block metadata.
-    let metadata = client.get_block_metadata(&block_id).await?;
-
-    if let Some(ms_index) = metadata.referenced_by_milestone_index {
-        let ms = client.get_milestone_by_index(ms_index).await?;
-        println!(
-            "Block {block_id} got confirmed by milestone {ms_index} at timestamp {}.",
-            ms.essence().timestamp()
-        );
-    } else {
-        println!("Block {block_id} is not confirmed.")
-    }
+    // Wait until the block has been confirmed.
+    let (_, ms_index) = client.retry_until_included(&block_id, None, None).await?;
+
+    let ms = client.get_milestone_by_index(ms_index).await?;
+    println!(
+        "Block {block_id} got confirmed by milestone {ms_index} at timestamp {}.",
+        ms.essence().timestamp()
+    );
 
     Ok(())
 }
@@ -10,7 +10,7 @@ use iota_types::{
     api::core::response::OutputWithMetadataResponse,
     block::{
         address::Address,
-        output::{Output, OutputMetadata},
+        output::{Output, OutputId, OutputMetadata},
         protocol::ProtocolParameters,
     },
 };
@@ -30,36 +30,37 @@ use crate::{
 impl<'a> ClientBlockBuilder<'a> {
     // Get basic outputs for an address without storage deposit return unlock condition
     pub(crate) async fn basic_address_outputs(&self, address: String) -> Result<Vec<OutputWithMetadataResponse>> {
-        let mut output_ids = Vec::new();
-
-        // First request to get all basic outputs that can directly be unlocked by the address.
-        output_ids.extend(
-            self.client
-                .basic_output_ids(vec![
-                    QueryParameter::Address(address.clone()),
-                    QueryParameter::HasStorageDepositReturn(false),
-                ])
-                .await?
-                .items,
-        );
-
-        // Second request to get all basic outputs that can be unlocked by the address through the expiration condition.
-        output_ids.extend(
-            self.client
-                .basic_output_ids(vec![
-                    QueryParameter::ExpirationReturnAddress(address),
-                    QueryParameter::HasExpiration(true),
-                    QueryParameter::HasStorageDepositReturn(false),
-                    // Ignore outputs that aren't expired yet
-                    QueryParameter::ExpiresBefore(unix_timestamp_now()),
-                ])
-                .await?
-                .items,
-        );
+        let output_ids = self.basic_address_output_ids(address).await?;
 
         self.client.get_outputs(output_ids).await
     }
 
+    // Get the ids of basic outputs for an address without storage deposit return unlock condition, cheaper than
+    // getting the outputs if only their existence is of interest
+    pub(crate) async fn basic_address_output_ids(&self, address: String) -> Result<Vec<OutputId>> {
+        // The first request gets all basic outputs that can directly be unlocked by the address, the second one all
+        // basic outputs that can be unlocked by the address through the expiration condition, both are sent at once.
+        let (address_output_ids, expiration_output_ids) = futures::future::try_join(
+            self.client.basic_output_ids(vec![
+                QueryParameter::Address(address.clone()),
+                QueryParameter::HasStorageDepositReturn(false),
+            ]),
+            self.client.basic_output_ids(vec![
+                QueryParameter::ExpirationReturnAddress(address),
+                QueryParameter::HasExpiration(true),
+                QueryParameter::HasStorageDepositReturn(false),
+                // Ignore outputs that aren't expired yet
+                QueryParameter::ExpiresBefore(unix_timestamp_now()),
+            ]),
+        )
+        .await?;
+
+        let mut output_ids = address_output_ids.items;
+        output_ids.extend(expiration_output_ids.items);
+
+        Ok(output_ids)
+    }
+
     /// Searches inputs for provided outputs, by requesting the outputs from the account addresses or for
     /// alias/foundry/nft outputs get the latest state with their alias/nft id. Forwards to [try_select_inputs()].
     pub(crate) async fn get_inputs(&self, protocol_parameters: &ProtocolParameters) -> Result<Selected> {
@@ -87,6 +88,7 @@ impl<'a> ClientBlockBuilder<'a> {
         available_inputs.dedup_by_key(|input| *input.output_id());
 
         let current_time = self.client.get_time_checked().await?;
+        let time_skew_tolerance = self.client.get_time_skew_tolerance().as_secs() as u32;
         // Assume that we own the addresses for inputs that are required for the provided outputs
         let mut available_input_addresses = Vec::new();
         for input in &available_inputs {
@@ -110,14 +112,17 @@ impl<'a> ClientBlockBuilder<'a> {
             protocol_parameters.clone(),
         )
         .required_inputs(required_inputs_for_sender_or_issuer_ids.clone())
-        .timestamp(current_time);
+        .timestamp(current_time)
+        .time_skew_tolerance(time_skew_tolerance);
 
         if let Some(address) = self.custom_remainder_address {
             input_selection = input_selection.remainder_address(address);
         }
 
-        if let Ok(selected_transaction_data) = input_selection.select() {
-            return Ok(selected_transaction_data);
+        if self.min_balance_reached(&available_inputs) {
+            if let Ok(selected_transaction_data) = input_selection.select() {
+                return Ok(selected_transaction_data);
+            }
         }
 
         log::debug!("[get_inputs from addresses]");
@@ -163,7 +168,8 @@ impl<'a> ClientBlockBuilder<'a> {
             let mut address_index = gap_index;
 
             for (index, (str_address, internal)) in public_and_internal_addresses.iter().enumerate() {
-                let address_outputs = self.basic_address_outputs(str_address.to_string()).await?;
+                // Only the output ids are requested first, so empty addresses don't need further requests.
+                let address_output_ids = self.basic_address_output_ids(str_address.to_string()).await?;
 
                 // If there are more than 20 (ADDRESS_GAP_RANGE) consecutive empty addresses, then we stop
                 // looking up the addresses belonging to the seed. Note that we don't
@@ -171,14 +177,14 @@ impl<'a> ClientBlockBuilder<'a> {
                 // unnecessary. We just need to check the address range,
                 // (index * ADDRESS_GAP_RANGE, index * ADDRESS_GAP_RANGE + ADDRESS_GAP_RANGE), where index is
                 // natural number, and to see if the outputs are all empty.
-                if address_outputs.is_empty() {
+                if address_output_ids.is_empty() {
                     // Accumulate the empty_address_count for each run of output address searching
                     empty_address_count += 1;
                 } else {
                     // Reset counter if there is an output
                     empty_address_count = 0;
 
-                    for output_response in address_outputs {
+                    for output_response in self.client.get_outputs(address_output_ids).await? {
                         let output = Output::try_from_dto(&output_response.output, token_supply)?;
                         let address = Address::try_from_bech32(str_address)?.1;
 
@@ -207,47 +213,51 @@ impl<'a> ClientBlockBuilder<'a> {
                     available_inputs.sort_unstable_by_key(|input| *input.output_id());
                     available_inputs.dedup_by_key(|input| *input.output_id());
 
-                    let mut input_selection = InputSelection::new(
-                        available_inputs.clone(),
-                        self.outputs.clone(),
-                        available_input_addresses.clone(),
-                        protocol_parameters.clone(),
-                    )
-                    .required_inputs(required_inputs_for_sender_or_issuer_ids.clone())
-                    .timestamp(current_time);
-
-                    if let Some(address) = self.custom_remainder_address {
-                        input_selection = input_selection.remainder_address(address);
-                    }
-
-                    let selected_transaction_data = match input_selection.select() {
-                        Ok(r) => r,
-                        // for these errors, just try again in the next round with more addresses which might have more
-                        // outputs.
-                        Err(err @ InputSelectionError::InsufficientAmount { .. }) => {
-                            cached_error.replace(Error::from(err));
-                            continue;
-                        }
-                        Err(err @ InputSelectionError::InsufficientNativeTokenAmount { .. }) => {
-                            cached_error.replace(Error::from(err));
-                            continue;
+                    // Inputs are only selected once the outputs found so far reach the minimum balance.
+                    if self.min_balance_reached(&available_inputs) {
+                        let mut input_selection = InputSelection::new(
+                            available_inputs.clone(),
+                            self.outputs.clone(),
+                            available_input_addresses.clone(),
+                            protocol_parameters.clone(),
+                        )
+                        .required_inputs(required_inputs_for_sender_or_issuer_ids.clone())
+                        .timestamp(current_time)
+                        .time_skew_tolerance(time_skew_tolerance);
+
+                        if let Some(address) = self.custom_remainder_address {
+                            input_selection = input_selection.remainder_address(address);
                         }
-                        Err(err @ InputSelectionError::NoAvailableInputsProvided { .. }) => {
-                            cached_error.replace(Error::from(err));
-                            continue;
-                        }
-                        // Not enough balance for a remainder.
-                        Err(InputSelectionError::Block(block_error)) => match block_error {
-                            iota_types::block::Error::InvalidStorageDepositAmount { .. } => {
-                                cached_error.replace(Error::from(InputSelectionError::Block(block_error)));
+
+                        let selected_transaction_data = match input_selection.select() {
+                            Ok(r) => r,
+                            // for these errors, just try again in the next round with more addresses which might have
+                            // more outputs.
+                            Err(err @ InputSelectionError::InsufficientAmount { .. }) => {
+                                cached_error.replace(Error::from(err));
                                 continue;
                             }
-                            _ => return Err(block_error.into()),
-                        },
-                        Err(e) => return Err(e)?,
-                    };
-
-                    break 'input_selection selected_transaction_data;
+                            Err(err @ InputSelectionError::InsufficientNativeTokenAmount { .. }) => {
+                                cached_error.replace(Error::from(err));
+                                continue;
+                            }
+                            Err(err @ InputSelectionError::NoAvailableInputsProvided) => {
+                                cached_error.replace(Error::from(err));
+                                continue;
+                            }
+                            // Not enough balance for a remainder.
+                            Err(InputSelectionError::Block(block_error)) => match block_error {
+                                iota_types::block::Error::InvalidStorageDepositAmount { .. } => {
+                                    cached_error.replace(Error::from(InputSelectionError::Block(block_error)));
+                                    continue;
+                                }
+                                _ => return Err(block_error.into()),
+                            },
+                            Err(e) => return Err(e)?,
+                        };
+
+                        break 'input_selection selected_transaction_data;
+                    }
                 }
 
                 // if we just processed an even index, increase the address index
@@ -261,6 +271,15 @@ impl<'a> ClientBlockBuilder<'a> {
 
             // The gap limit is 20 and use reference 40 here because there's public and internal addresses
             if empty_address_count >= (ADDRESS_GAP_RANGE * 2) as u64 {
+                if let Some(min_balance) = self.min_balance {
+                    let balance = inputs_amount(&available_inputs);
+                    if balance < min_balance {
+                        return Err(Error::InsufficientBalanceForCondition {
+                            found: balance,
+                            required: min_balance,
+                        });
+                    }
+                }
                 // returned last cached error
                 return Err(cached_error.unwrap_or_else(|| Error::from(InputSelectionError::NoAvailableInputsProvided)));
             }
@@ -268,4 +287,14 @@ impl<'a> ClientBlockBuilder<'a> {
 
         Ok(selected_transaction_data)
     }
+
+    // Whether the outputs found so far reach the minimum balance, if one is required.
+    fn min_balance_reached(&self, available_inputs: &[InputSigningData]) -> bool {
+        inputs_amount(available_inputs) >= self.min_balance.unwrap_or_default()
+    }
+}
+
+// Sums the amount of the available inputs.
+fn inputs_amount(available_inputs: &[InputSigningData]) -> u64 {
+    available_inputs.iter().map(|input| input.output.amount()).sum()
 }
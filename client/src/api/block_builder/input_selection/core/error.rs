@@ -5,7 +5,10 @@
 
 use std::fmt::{Debug, Display};
 
-use iota_types::block::output::{ChainId, OutputId, TokenId};
+use iota_types::block::{
+    address::Address,
+    output::{ChainId, OutputId, TokenId},
+};
 use primitive_types::U256;
 use serde::{Serialize, Serializer};
 
@@ -40,6 +43,9 @@ pub enum Error {
         /// The required amount.
         required: U256,
     },
+    /// The remainder address can't receive a remainder.
+    #[error("invalid remainder address {0:?}, only ed25519 addresses can receive a remainder")]
+    InvalidRemainderAddress(Address),
     /// No input with matching ed25519 address provided.
     #[error("no input with matching ed25519 address provided")]
     MissingInputWithEd25519Address,
@@ -21,7 +21,7 @@ use crate::{
     api::types::RemainderData,
     block::{
         address::{Address, AliasAddress, NftAddress},
-        output::{AliasTransition, ChainId, Output, OutputId},
+        output::{AliasTransition, ChainId, NativeTokens, Output, OutputId},
         protocol::ProtocolParameters,
     },
     secret::types::InputSigningData,
@@ -43,12 +43,16 @@ pub struct InputSelection {
     remainder_address: Option<Address>,
     protocol_parameters: ProtocolParameters,
     timestamp: u32,
+    time_skew_tolerance: u32,
+    min_confirmations: u32,
+    prefer_expiring_soon: bool,
+    max_native_tokens_per_remainder: u8,
     requirements: Vec<Requirement>,
     automatically_transitioned: HashMap<ChainId, Option<AliasTransition>>,
 }
 
 /// Result of the input selection algorithm.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Selected {
     /// Selected inputs.
     pub inputs: Vec<InputSigningData>,
@@ -56,6 +60,10 @@ pub struct Selected {
     pub outputs: Vec<Output>,
     /// Remainder, if there was one.
     pub remainder: Option<RemainderData>,
+    /// Additional remainders for the native tokens that didn't fit into the remainder, see
+    /// [`InputSelection::max_native_tokens_per_remainder()`].
+    #[serde(rename = "additionalRemainders", default)]
+    pub additional_remainders: Vec<RemainderData>,
 }
 
 impl InputSelection {
@@ -188,6 +196,10 @@ impl InputSelection {
             remainder_address: None,
             protocol_parameters,
             timestamp: unix_timestamp_now(),
+            time_skew_tolerance: 0,
+            min_confirmations: 0,
+            prefer_expiring_soon: false,
+            max_native_tokens_per_remainder: NativeTokens::COUNT_MAX,
             requirements: Vec::new(),
             automatically_transitioned: HashMap::new(),
         }
@@ -211,7 +223,8 @@ impl InputSelection {
         self
     }
 
-    /// Sets the remainder address of an [`InputSelection`].
+    /// Sets the remainder address of an [`InputSelection`], which has to be an Ed25519 address. If none is set, the
+    /// remainder is sent to the Ed25519 address unlocking the first selected input that has one.
     pub fn remainder_address(mut self, address: Address) -> Self {
         self.remainder_address.replace(address);
         self
@@ -223,8 +236,49 @@ impl InputSelection {
         self
     }
 
+    /// Sets the tolerance in seconds for a difference between the timestamp and the time of the node of an
+    /// [`InputSelection`]. Inputs with a timelock or an expiration within this tolerance of the timestamp are not
+    /// selected, since the node could evaluate them differently.
+    pub fn time_skew_tolerance(mut self, time_skew_tolerance: u32) -> Self {
+        self.time_skew_tolerance = time_skew_tolerance;
+        self
+    }
+
+    /// Sets the minimum number of milestones that need to have been confirmed since an input got booked for it to be
+    /// selected by an [`InputSelection`]. The age of an input is the difference between the ledger index and the
+    /// milestone index booked of its metadata. Younger inputs could still be affected by a reorganization.
+    pub fn min_confirmations(mut self, min_confirmations: u32) -> Self {
+        self.min_confirmations = min_confirmations;
+        self
+    }
+
+    /// Sets whether an [`InputSelection`] should first select the inputs that expire the soonest to fulfill the amount,
+    /// so they are spent before they can only be unlocked by their return address.
+    pub fn prefer_expiring_soon(mut self, prefer_expiring_soon: bool) -> Self {
+        self.prefer_expiring_soon = prefer_expiring_soon;
+        self
+    }
+
+    /// Sets the maximum number of distinct native tokens a remainder output of an [`InputSelection`] holds, at most
+    /// the protocol maximum of native tokens per output. If more native tokens are left, they are split over multiple
+    /// remainder outputs.
+    pub fn max_native_tokens_per_remainder(mut self, max_native_tokens: u8) -> Self {
+        self.max_native_tokens_per_remainder = max_native_tokens.clamp(1, NativeTokens::COUNT_MAX);
+        self
+    }
+
     fn filter_inputs(&mut self) {
         self.available_inputs.retain(|input| {
+            // Filter out inputs that have been booked too recently.
+            if input
+                .output_metadata
+                .ledger_index()
+                .saturating_sub(input.output_metadata.milestone_index_booked())
+                < self.min_confirmations
+            {
+                return false;
+            }
+
             // Keep alias outputs because at this point we do not know if a state or governor address will be required.
             if input.output.is_alias() {
                 return true;
@@ -237,7 +291,13 @@ impl InputSelection {
             // PANIC: safe to unwrap as non basic/alias/foundry/nft outputs are already filtered out.
             let unlock_conditions = input.output.unlock_conditions().unwrap();
 
-            if unlock_conditions.is_time_locked(self.timestamp) {
+            let earliest_timestamp = self.timestamp.saturating_sub(self.time_skew_tolerance);
+            let latest_timestamp = self.timestamp.saturating_add(self.time_skew_tolerance);
+
+            // Also filter out inputs that could still be time locked or that could expire for the node.
+            if unlock_conditions.is_time_locked(earliest_timestamp)
+                || unlock_conditions.is_expired(earliest_timestamp) != unlock_conditions.is_expired(latest_timestamp)
+            {
                 return false;
             }
 
@@ -367,6 +427,11 @@ impl InputSelection {
         if self.outputs.is_empty() && self.burn.is_none() {
             return Err(Error::NoOutputsProvided);
         }
+        if let Some(remainder_address) = self.remainder_address {
+            if !remainder_address.is_ed25519() {
+                return Err(Error::InvalidRemainderAddress(remainder_address));
+            }
+        }
 
         // Creates the initial state, selected inputs and requirements, based on the provided outputs.
         self.init()?;
@@ -382,11 +447,13 @@ impl InputSelection {
             }
         }
 
-        let (remainder, storage_deposit_returns) = self.remainder_and_storage_deposit_return_outputs()?;
+        let (remainders, storage_deposit_returns) = self.remainder_and_storage_deposit_return_outputs()?;
 
-        if let Some(remainder) = &remainder {
-            self.outputs.push(remainder.output.clone());
-        }
+        self.outputs
+            .extend(remainders.iter().map(|remainder| remainder.output.clone()));
+        let mut remainders = remainders.into_iter();
+        let remainder = remainders.next();
+        let additional_remainders = remainders.collect();
 
         self.outputs.extend(storage_deposit_returns);
 
@@ -394,6 +461,7 @@ impl InputSelection {
             inputs: Self::sort_input_signing_data(self.selected_inputs, &self.outputs, Some(self.timestamp))?,
             outputs: self.outputs,
             remainder,
+            additional_remainders,
         })
     }
 }
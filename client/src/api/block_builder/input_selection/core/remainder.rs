@@ -15,7 +15,7 @@ use crate::{
         address::{Address, Ed25519Address},
         output::{
             unlock_condition::{AddressUnlockCondition, UnlockCondition},
-            BasicOutputBuilder, NativeTokensBuilder, Output,
+            BasicOutputBuilder, NativeToken, NativeTokensBuilder, Output,
         },
     },
     crypto::keys::slip10::Chain,
@@ -62,27 +62,45 @@ impl InputSelection {
         let native_tokens_diff = get_native_tokens_diff(&input_native_tokens, &output_native_tokens)?;
         let native_tokens_remainder = native_tokens_diff.is_some();
 
-        let mut remainder_builder =
-            BasicOutputBuilder::new_with_minimum_storage_deposit(self.protocol_parameters.rent_structure().clone())?
-                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::from(
-                    Ed25519Address::from([0; 32]),
-                ))));
+        // The storage deposit of the remainder, or of all of them if the native tokens need to be split. The address
+        // doesn't change the storage deposit, so a placeholder is used.
+        let address = Address::from(Ed25519Address::from([0; 32]));
+        let mut remainder_amount = 0;
+        for native_tokens in self.native_tokens_remainder_chunks(native_tokens_diff.unwrap_or_default()) {
+            remainder_amount += self.minimum_remainder_output(address, native_tokens)?.amount();
+        }
 
-        if let Some(native_tokens) = native_tokens_diff {
-            remainder_builder = remainder_builder.with_native_tokens(native_tokens);
+        Ok((remainder_amount, native_tokens_remainder))
+    }
+
+    // Splits the leftover native tokens into the ones for each remainder output. There is always at least one, possibly
+    // empty, chunk.
+    fn native_tokens_remainder_chunks(&self, native_tokens: Vec<NativeToken>) -> Vec<Vec<NativeToken>> {
+        if native_tokens.is_empty() {
+            return vec![native_tokens];
         }
 
-        Ok((
-            remainder_builder
-                .finish_output(self.protocol_parameters.token_supply())?
-                .amount(),
-            native_tokens_remainder,
-        ))
+        native_tokens
+            .chunks(self.max_native_tokens_per_remainder as usize)
+            .map(<[NativeToken]>::to_vec)
+            .collect()
+    }
+
+    // Builds a remainder output with the minimum storage deposit for the given native tokens.
+    fn minimum_remainder_output(&self, address: Address, native_tokens: Vec<NativeToken>) -> Result<Output, Error> {
+        Ok(
+            BasicOutputBuilder::new_with_minimum_storage_deposit(self.protocol_parameters.rent_structure().clone())?
+                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+                .with_native_tokens(native_tokens)
+                .finish_output(self.protocol_parameters.token_supply())?,
+        )
     }
 
+    // Returns the remainders and the storage deposit return outputs. The first remainder holds the rest of the amount,
+    // the others the native tokens that don't fit into it.
     pub(crate) fn remainder_and_storage_deposit_return_outputs(
         &self,
-    ) -> Result<(Option<RemainderData>, Vec<Output>), Error> {
+    ) -> Result<(Vec<RemainderData>, Vec<Output>), Error> {
         let (inputs_sum, outputs_sum, inputs_sdr, outputs_sdr) =
             amount_sums(&self.selected_inputs, &self.outputs, self.timestamp);
         let mut storage_deposit_returns = Vec::new();
@@ -120,21 +138,46 @@ impl InputSelection {
 
         if inputs_sum == outputs_sum && native_tokens_diff.is_none() {
             log::debug!("No remainder required");
-            return Ok((None, storage_deposit_returns));
+            return Ok((Vec::new(), storage_deposit_returns));
         }
 
         let Some((remainder_address, chain)) = self.get_remainder_address() else {
             return Err(Error::MissingInputWithEd25519Address);
         };
 
+        let mut native_tokens_chunks = self
+            .native_tokens_remainder_chunks(native_tokens_diff.unwrap_or_default())
+            .into_iter();
+        // PANIC: there is always at least one chunk.
+        let native_tokens = native_tokens_chunks.next().unwrap();
+
+        // Native tokens that don't fit into the remainder go to additional outputs with the minimum storage deposit.
+        let mut native_tokens_remainders = Vec::new();
+        for native_tokens in native_tokens_chunks {
+            let output = self.minimum_remainder_output(remainder_address, native_tokens)?;
+
+            log::debug!(
+                "Created additional remainder output of {} for {remainder_address:?}",
+                output.amount()
+            );
+
+            native_tokens_remainders.push(output);
+        }
+        let native_tokens_remainders_amount = native_tokens_remainders.iter().map(Output::amount).sum::<u64>();
+
         // TODO checked ops ?
-        let diff = inputs_sum - outputs_sum;
+        let diff = (inputs_sum - outputs_sum)
+            .checked_sub(native_tokens_remainders_amount)
+            .ok_or(Error::InsufficientAmount {
+                found: inputs_sum,
+                required: outputs_sum + native_tokens_remainders_amount,
+            })?;
         let mut remainder_builder = BasicOutputBuilder::new_with_amount(diff)?;
 
         remainder_builder = remainder_builder
             .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(remainder_address)));
 
-        if let Some(native_tokens) = native_tokens_diff {
+        if !native_tokens.is_empty() {
             log::debug!("Adding {native_tokens:?} to remainder output for {remainder_address:?}");
             remainder_builder = remainder_builder.with_native_tokens(native_tokens);
         }
@@ -148,13 +191,15 @@ impl InputSelection {
             self.protocol_parameters.token_supply(),
         )?;
 
-        Ok((
-            Some(RemainderData {
-                output: remainder,
-                chain,
+        let remainders = std::iter::once(remainder)
+            .chain(native_tokens_remainders)
+            .map(|output| RemainderData {
+                output,
+                chain: chain.clone(),
                 address: remainder_address,
-            }),
-            storage_deposit_returns,
-        ))
+            })
+            .collect();
+
+        Ok((remainders, storage_deposit_returns))
     }
 }
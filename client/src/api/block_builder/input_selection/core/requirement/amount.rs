@@ -32,6 +32,15 @@ pub(crate) fn sdruc_not_expired(output: &Output, current_time: u32) -> Option<&S
     })
 }
 
+// Get the timestamp of the expiration unlock condition, if the output hasn't expired yet.
+fn upcoming_expiration(output: &Output, current_time: u32) -> Option<u32> {
+    output
+        .unlock_conditions()
+        .and_then(|unlock_conditions| unlock_conditions.expiration())
+        .map(|expiration| expiration.timestamp())
+        .filter(|timestamp| current_time < *timestamp)
+}
+
 pub(crate) fn amount_sums(
     selected_inputs: &[InputSigningData],
     outputs: &[Output],
@@ -299,6 +308,15 @@ impl InputSelection {
         self.available_inputs
             .sort_by(|left, right| left.output.amount().cmp(&right.output.amount()));
 
+        if self.prefer_expiring_soon {
+            // Stable sort, so inputs with the same or without expiration stay sorted by amount.
+            let timestamp = self.timestamp;
+            self.available_inputs.sort_by_key(|input| {
+                let expiration = upcoming_expiration(&input.output, timestamp);
+                (expiration.is_none(), expiration)
+            });
+        }
+
         'fulfil: {
             let basic_ed25519_inputs = self.available_inputs.iter().filter(|input| {
                 if let Output::Basic(output) = &input.output {
@@ -7,7 +7,7 @@ use primitive_types::U256;
 
 use super::{Error, InputSelection};
 use crate::{
-    block::output::{AliasTransition, NativeToken, NativeTokens, NativeTokensBuilder, Output, TokenScheme},
+    block::output::{AliasTransition, NativeToken, NativeTokensBuilder, Output, TokenScheme},
     secret::types::InputSigningData,
 };
 
@@ -88,7 +88,7 @@ pub(crate) fn get_minted_and_melted_native_tokens(
 pub(crate) fn get_native_tokens_diff(
     inputs: &NativeTokensBuilder,
     outputs: &NativeTokensBuilder,
-) -> Result<Option<NativeTokens>, Error> {
+) -> Result<Option<Vec<NativeToken>>, Error> {
     let mut native_tokens_diff = NativeTokensBuilder::new();
 
     for (token_id, input_amount) in inputs.iter() {
@@ -107,7 +107,11 @@ pub(crate) fn get_native_tokens_diff(
     if native_tokens_diff.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(native_tokens_diff.finish()?))
+        let mut native_tokens_diff = native_tokens_diff.finish_vec()?;
+        // Sorted like in `NativeTokens`, so they are split deterministically if they don't fit into a single output.
+        native_tokens_diff.sort_by_key(|native_token| *native_token.token_id());
+
+        Ok(Some(native_tokens_diff))
     }
 }
 
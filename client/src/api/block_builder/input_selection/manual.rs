@@ -38,6 +38,7 @@ impl<'a> ClientBlockBuilder<'a> {
 
         let mut inputs_data = Vec::new();
         let current_time = self.client.get_time_checked().await?;
+        let time_skew_tolerance = self.client.get_time_skew_tolerance().as_secs() as u32;
         let token_supply = self.client.get_token_supply().await?;
 
         if let Some(inputs) = &self.inputs {
@@ -123,7 +124,8 @@ impl<'a> ClientBlockBuilder<'a> {
             protocol_parameters.clone(),
         )
         .required_inputs(required_inputs)
-        .timestamp(current_time);
+        .timestamp(current_time)
+        .time_skew_tolerance(time_skew_tolerance);
 
         if let Some(address) = self.custom_remainder_address {
             input_selection = input_selection.remainder_address(address);
@@ -9,9 +9,10 @@ use std::ops::Range;
 
 use iota_types::block::{
     address::{Address, Ed25519Address},
-    input::{dto::UtxoInputDto, UtxoInput, INPUT_COUNT_MAX},
+    input::{dto::UtxoInputDto, UtxoInput},
     output::{
         dto::OutputDto,
+        feature::{Feature, TagFeature},
         unlock_condition::{AddressUnlockCondition, UnlockCondition},
         BasicOutputBuilder, Output, OUTPUT_COUNT_RANGE,
     },
@@ -21,7 +22,7 @@ use iota_types::block::{
 };
 use packable::bounded::TryIntoBoundedU16Error;
 
-pub use self::transaction::verify_semantic;
+pub use self::transaction::{sign_prepared_transaction, verify_semantic};
 use crate::{
     api::block_builder::input_selection::Burn, constants::SHIMMER_COIN_TYPE, secret::SecretManager, Client, Error,
     Result,
@@ -38,11 +39,14 @@ pub struct ClientBlockBuilder<'a> {
     inputs: Option<Vec<UtxoInput>>,
     input_range: Range<u32>,
     outputs: Vec<Output>,
+    output_tag: Option<TagFeature>,
     custom_remainder_address: Option<Address>,
     tag: Option<Vec<u8>>,
     data: Option<Vec<u8>>,
     parents: Option<Parents>,
+    max_parents: Option<u8>,
     burn: Option<Burn>,
+    min_balance: Option<u64>,
 }
 
 /// Block output address
@@ -74,6 +78,8 @@ pub struct ClientBlockBuilderOptions {
     pub output: Option<ClientBlockBuilderOutputAddress>,
     /// Hex encoded output address and amount
     pub output_hex: Option<ClientBlockBuilderOutputAddress>,
+    /// Hex encoded tag for the outputs created from `output` and `output_hex`
+    pub output_tag: Option<String>,
     /// Outputs
     pub outputs: Option<Vec<OutputDto>>,
     /// Custom remainder address
@@ -84,6 +90,8 @@ pub struct ClientBlockBuilderOptions {
     pub data: Option<String>,
     /// Parents
     pub parents: Option<Vec<BlockId>>,
+    /// Maximum amount of tips used as parents, if no parents are provided
+    pub max_parents: Option<u8>,
     /// Explicit burning of aliases, nfts, foundries and native tokens
     pub burn: Option<Burn>,
 }
@@ -100,11 +108,14 @@ impl<'a> ClientBlockBuilder<'a> {
             inputs: None,
             input_range: 0..100,
             outputs: Vec::new(),
+            output_tag: None,
             custom_remainder_address: None,
             tag: None,
             data: None,
             parents: None,
+            max_parents: None,
             burn: None,
+            min_balance: None,
         }
     }
 
@@ -143,9 +154,12 @@ impl<'a> ClientBlockBuilder<'a> {
         self.inputs = match self.inputs {
             Some(mut inputs) => {
                 inputs.push(input);
-                // 128 is the maximum input amount
-                if inputs.len() > INPUT_COUNT_MAX.into() {
-                    return Err(Error::ConsolidationRequired(inputs.len()));
+                let input_limit = self.client.get_consolidation_threshold();
+                if inputs.len() > input_limit {
+                    return Err(Error::ConsolidationRequired {
+                        limit: input_limit,
+                        needed: inputs.len(),
+                    });
                 }
                 Some(inputs)
             }
@@ -160,20 +174,17 @@ impl<'a> ClientBlockBuilder<'a> {
         self
     }
 
+    /// Sets the minimum amount the outputs found by the automatic input selection need to reach, otherwise
+    /// [`Error::InsufficientBalanceForCondition`] is returned before anything is signed.
+    pub(crate) fn with_min_balance(mut self, min_balance: u64) -> Self {
+        self.min_balance.replace(min_balance);
+        self
+    }
+
     /// Set a transfer to the builder
-    pub async fn with_output(mut self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
-        let output = BasicOutputBuilder::new_with_amount(amount)?
-            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
-                Address::try_from_bech32(address)?.1,
-            )))
-            .finish_output(self.client.get_token_supply().await?)?;
-        self.outputs.push(output);
-        if !OUTPUT_COUNT_RANGE.contains(&(self.outputs.len() as u16)) {
-            return Err(crate::Error::Block(iota_types::block::Error::InvalidOutputCount(
-                TryIntoBoundedU16Error::Truncated(self.outputs.len()),
-            )));
-        }
-        Ok(self)
+    pub async fn with_output(self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
+        let address = Address::try_from_bech32(address)?.1;
+        self.with_basic_output(address, amount).await
     }
 
     /// Set outputs to the builder
@@ -188,12 +199,19 @@ impl<'a> ClientBlockBuilder<'a> {
     }
 
     /// Set a transfer to the builder, address needs to be hex encoded
-    pub async fn with_output_hex(mut self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
-        let output = BasicOutputBuilder::new_with_amount(amount)?
-            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
-                address.parse::<Ed25519Address>()?.into(),
-            )))
-            .finish_output(self.client.get_token_supply().await?)?;
+    pub async fn with_output_hex(self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
+        let address = address.parse::<Ed25519Address>()?.into();
+        self.with_basic_output(address, amount).await
+    }
+
+    // Adds a basic output to the address, tagged with the output tag if one is set.
+    async fn with_basic_output(mut self, address: Address, amount: u64) -> Result<ClientBlockBuilder<'a>> {
+        let mut output_builder = BasicOutputBuilder::new_with_amount(amount)?
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
+        if let Some(tag) = &self.output_tag {
+            output_builder = output_builder.add_feature(Feature::Tag(tag.clone()));
+        }
+        let output = output_builder.finish_output(self.client.get_token_supply().await?)?;
         self.outputs.push(output);
         if !OUTPUT_COUNT_RANGE.contains(&(self.outputs.len() as u16)) {
             return Err(crate::Error::Block(iota_types::block::Error::InvalidOutputCount(
@@ -203,6 +221,13 @@ impl<'a> ClientBlockBuilder<'a> {
         Ok(self)
     }
 
+    /// Set a tag feature for the outputs that are created afterwards with [`ClientBlockBuilder::with_output()`] and
+    /// [`ClientBlockBuilder::with_output_hex()`]
+    pub fn with_output_tag(mut self, tag: Vec<u8>) -> Result<Self> {
+        self.output_tag.replace(TagFeature::new(tag)?);
+        Ok(self)
+    }
+
     /// Set a custom remainder address
     pub fn with_custom_remainder_address(mut self, address: &str) -> Result<Self> {
         let address = Address::try_from_bech32(address)?.1;
@@ -211,6 +236,8 @@ impl<'a> ClientBlockBuilder<'a> {
     }
 
     /// Set tagged_data to the builder
+    ///
+    /// Tags longer than the protocol maximum make [`ClientBlockBuilder::finish()`] fail with [`Error::TagTooLong`].
     pub fn with_tag(mut self, tag: Vec<u8>) -> Self {
         self.tag.replace(tag);
         self
@@ -222,6 +249,16 @@ impl<'a> ClientBlockBuilder<'a> {
         self
     }
 
+    /// Set the amount of tips to use as parents, capped to 1-8. Finishing the block fails if the node offers fewer
+    /// tips. Has no effect if parents are set with [`ClientBlockBuilder::with_parents()`].
+    ///
+    /// The tips are fetched once, so unlike the default they aren't refreshed during a long local proof of work.
+    pub fn with_max_parents(mut self, max_parents: u8) -> Self {
+        self.max_parents
+            .replace(max_parents.clamp(*Parents::COUNT_RANGE.start(), *Parents::COUNT_RANGE.end()));
+        self
+    }
+
     /// Set 1-8 custom parent block ids
     pub fn with_parents(mut self, parent_ids: Vec<BlockId>) -> Result<Self> {
         self.parents.replace(Parents::new(parent_ids)?);
@@ -253,6 +290,10 @@ impl<'a> ClientBlockBuilder<'a> {
             self = self.with_input_range(input_range);
         }
 
+        if let Some(output_tag) = options.output_tag {
+            self = self.with_output_tag(prefix_hex::decode(output_tag)?)?;
+        }
+
         if let Some(output) = options.output {
             self = self
                 .with_output(
@@ -303,6 +344,9 @@ impl<'a> ClientBlockBuilder<'a> {
         if let Some(parents) = options.parents {
             self = self.with_parents(parents)?;
         }
+        if let Some(max_parents) = options.max_parents {
+            self = self.with_max_parents(max_parents);
+        }
         if let Some(burn) = options.burn {
             self = self.with_burn(burn);
         }
@@ -338,6 +382,14 @@ impl<'a> ClientBlockBuilder<'a> {
 
     /// Consume the builder and get the API result
     pub async fn finish_tagged_data(self) -> Result<Block> {
+        let max_tag_length = *TaggedDataPayload::TAG_LENGTH_RANGE.end() as usize;
+        if let Some(tag) = self.tag.as_ref().filter(|tag| tag.len() > max_tag_length) {
+            return Err(Error::TagTooLong {
+                length: tag.len(),
+                max_length: max_tag_length,
+            });
+        }
+
         let payload: Payload;
         {
             let index = &self.tag.as_ref();
@@ -354,13 +406,36 @@ impl<'a> ClientBlockBuilder<'a> {
         self.finish_block(Some(payload)).await
     }
 
+    // Gets `max_parents` of the tips of the node, in the order the node returned them.
+    async fn tips_as_parents(&self, max_parents: u8) -> Result<Parents> {
+        let mut tips = Vec::new();
+        for tip in self.client.get_tips().await? {
+            if !tips.contains(&tip) {
+                tips.push(tip);
+            }
+        }
+        if tips.len() < max_parents as usize {
+            return Err(Error::InsufficientTips {
+                requested: max_parents,
+                available: tips.len(),
+            });
+        }
+        tips.truncate(max_parents as usize);
+
+        Ok(Parents::new(tips)?)
+    }
+
     /// Builds the final block and posts it to the node
     pub async fn finish_block(self, payload: Option<Payload>) -> Result<Block> {
+        let parents = match self.max_parents {
+            Some(max_parents) if self.parents.is_none() => Some(self.tips_as_parents(max_parents).await?),
+            _ => self.parents,
+        };
         // Do not replace parents with the latest tips if they are set explicitly,
         // necessary for block promotion.
-        let final_block = self.client.finish_block_builder(self.parents, payload).await?;
+        let final_block = self.client.finish_block_builder(parents, payload).await?;
 
-        let block_id = self.client.post_block_raw(&final_block).await?;
+        let block_id = self.client.submit_block(&final_block).await?;
         // Get block if we use remote PoW, because the node will change parents and nonce
         if self.client.get_local_pow() {
             Ok(final_block)
@@ -10,16 +10,16 @@ use iota_types::block::{
         transaction::{RegularTransactionEssence, TransactionEssence, TransactionPayload},
         Payload, TaggedDataPayload,
     },
-    semantic::{semantic_validation, ConflictReason, ValidationContext},
+    semantic::{semantic_validation, semantic_validation_unsigned, ConflictReason, ValidationContext},
     signature::Ed25519Signature,
     Block, BlockId,
 };
 use packable::PackableExt;
 
 use crate::{
-    api::{types::PreparedTransactionData, ClientBlockBuilder},
-    secret::{types::InputSigningData, SecretManageExt},
-    Error, Result,
+    api::{types::PreparedTransactionData, ClientBlockBuilder, ClientBlockBuilderOptions},
+    secret::{types::InputSigningData, SecretManageExt, SecretManager},
+    unix_timestamp_now, Client, Error, Result,
 };
 
 const MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS: usize = Block::LENGTH_MAX - Block::LENGTH_MIN - (7 * BlockId::LENGTH);
@@ -48,6 +48,14 @@ impl<'a> ClientBlockBuilder<'a> {
             self.get_inputs(&protocol_parameters).await?
         };
 
+        let input_limit = self.client.get_consolidation_threshold();
+        if selected_transaction_data.inputs.len() > input_limit {
+            return Err(Error::ConsolidationRequired {
+                limit: input_limit,
+                needed: selected_transaction_data.inputs.len(),
+            });
+        }
+
         // Build transaction payload
         let inputs_commitment = InputsCommitment::new(selected_transaction_data.inputs.iter().map(|i| &i.output));
 
@@ -91,24 +99,62 @@ impl<'a> ClientBlockBuilder<'a> {
         let secret_manager = self.secret_manager.ok_or(Error::MissingParameter("secret manager"))?;
         let current_time = self.client.get_time_checked().await?;
 
-        let unlocks = secret_manager
-            .sign_transaction_essence(&prepared_transaction_data, Some(current_time))
-            .await?;
-        let tx_payload = TransactionPayload::new(prepared_transaction_data.essence.clone(), unlocks)?;
+        let tx_payload = sign_transaction_at(&prepared_transaction_data, secret_manager, current_time).await?;
 
-        validate_transaction_payload_length(&tx_payload)?;
-
-        let conflict = verify_semantic(&prepared_transaction_data.inputs_data, &tx_payload, current_time)?;
+        Ok(Payload::from(tx_payload))
+    }
+}
 
-        if conflict != ConflictReason::None {
-            log::debug!("[sign_transaction] conflict: {conflict:?} for {:#?}", tx_payload);
-            return Err(Error::TransactionSemantic(conflict));
+impl Client {
+    /// Prepare a transaction for offline signing with [`sign_prepared_transaction()`]. The secret manager is only
+    /// needed if no inputs are provided in the options, to search them.
+    pub async fn prepare_transaction(
+        &self,
+        secret_manager: Option<&SecretManager>,
+        options: ClientBlockBuilderOptions,
+    ) -> Result<PreparedTransactionData> {
+        let mut block_builder = self.block();
+
+        if let Some(secret_manager) = secret_manager {
+            block_builder = block_builder.with_secret_manager(secret_manager);
         }
 
-        Ok(Payload::from(tx_payload))
+        block_builder.set_options(options).await?.prepare_transaction().await
     }
 }
 
+/// Sign a transaction prepared with [`Client::prepare_transaction()`], without any network access. The local time
+/// is used to check the time locks and expirations of the inputs.
+pub async fn sign_prepared_transaction(
+    prepared_transaction_data: &PreparedTransactionData,
+    secret_manager: &SecretManager,
+) -> Result<TransactionPayload> {
+    log::debug!("[sign_prepared_transaction] {:?}", prepared_transaction_data);
+    sign_transaction_at(prepared_transaction_data, secret_manager, unix_timestamp_now()).await
+}
+
+async fn sign_transaction_at(
+    prepared_transaction_data: &PreparedTransactionData,
+    secret_manager: &SecretManager,
+    current_time: u32,
+) -> Result<TransactionPayload> {
+    let unlocks = secret_manager
+        .sign_transaction_essence(prepared_transaction_data, Some(current_time))
+        .await?;
+    let tx_payload = TransactionPayload::new(prepared_transaction_data.essence.clone(), unlocks)?;
+
+    validate_transaction_payload_length(&tx_payload)?;
+
+    let conflict = verify_semantic(&prepared_transaction_data.inputs_data, &tx_payload, current_time)?;
+
+    if conflict != ConflictReason::None {
+        log::debug!("[sign_transaction] conflict: {conflict:?} for {:#?}", tx_payload);
+        return Err(Error::TransactionSemantic(conflict));
+    }
+
+    Ok(tx_payload)
+}
+
 // TODO @thibault-martinez: this is very cumbersome with the current state, will refactor.
 /// Verifies the semantic of a prepared transaction.
 pub fn verify_semantic(
@@ -176,3 +222,23 @@ pub fn validate_regular_transaction_essence_length(
     }
     Ok(())
 }
+
+/// Verifies the semantic of a prepared transaction that isn't signed yet. The unlocks aren't verified, the addresses
+/// the inputs are locked to are considered unlocked instead; everything else, like the balance of the amounts and
+/// native tokens, storage deposit returns and chain transitions, is validated like for a signed transaction.
+pub(crate) fn verify_unsigned_semantic(
+    prepared_transaction_data: &PreparedTransactionData,
+    current_time: u32,
+) -> Result<ConflictReason> {
+    let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+
+    validate_regular_transaction_essence_length(essence)?;
+
+    let inputs = prepared_transaction_data
+        .inputs_data
+        .iter()
+        .map(|input| (*input.output_id(), &input.output))
+        .collect::<Vec<(OutputId, &Output)>>();
+
+    Ok(semantic_validation_unsigned(essence, &inputs, current_time)?)
+}
@@ -3,19 +3,25 @@
 
 use std::str::FromStr;
 
-use iota_types::block::{
-    address::Address,
-    input::{UtxoInput, INPUT_COUNT_MAX},
-    output::{
-        unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeTokensBuilder, Output, OutputId,
-        UnlockCondition,
+use iota_types::{
+    api::core::response::OutputWithMetadataResponse,
+    block::{
+        address::Address,
+        input::{UtxoInput, INPUT_COUNT_MAX},
+        output::{
+            unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeTokensBuilder, Output, OutputId,
+            UnlockCondition,
+        },
+        payload::transaction::TransactionId,
+        Block,
     },
-    payload::transaction::TransactionId,
 };
 
 use crate::{
-    api::GetAddressesBuilderOptions, node_api::indexer::query_parameters::QueryParameter, secret::SecretManager,
-    Client, Result,
+    api::{input_selection::Error as InputSelectionError, ConsolidationPlan, GetAddressesBuilderOptions},
+    node_api::indexer::query_parameters::QueryParameter,
+    secret::SecretManager,
+    Client, Error, Result,
 };
 
 impl Client {
@@ -47,17 +53,7 @@ impl Client {
                 // add the offset so the index matches the address index also for higher start indexes
                 let index = index + offset;
 
-                // Get output ids of outputs that can be controlled by this address without further unlock constraints
-                let output_ids_response = self
-                    .basic_output_ids(vec![
-                        QueryParameter::Address(address.to_string()),
-                        QueryParameter::HasExpiration(false),
-                        QueryParameter::HasTimelock(false),
-                        QueryParameter::HasStorageDepositReturn(false),
-                    ])
-                    .await?;
-
-                let basic_outputs_responses = self.get_outputs(output_ids_response.items).await?;
+                let basic_outputs_responses = self.consolidation_inputs(address).await?;
 
                 if !basic_outputs_responses.is_empty() {
                     // If we reach the same index again
@@ -118,4 +114,139 @@ impl Client {
         }
         Ok(consolidation_address)
     }
+
+    /// Function to preview [`Client::consolidate_funds()`] without sending any block. Returns the amount and total
+    /// amount of the outputs in the range of addresses that can be consolidated, and the address they would be
+    /// consolidated to. Fails with [`Error::MissingParameter`] if the range of addresses is empty.
+    pub async fn preview_consolidation(
+        &self,
+        secret_manager: &SecretManager,
+        address_builder_options: GetAddressesBuilderOptions,
+    ) -> Result<ConsolidationPlan> {
+        let token_supply = self.get_token_supply().await?;
+        let addresses = self
+            .get_addresses(secret_manager)
+            .set_options(address_builder_options)?
+            .finish()
+            .await?;
+        let target_address = addresses
+            .first()
+            .ok_or(Error::MissingParameter("address range"))?
+            .clone();
+
+        let mut input_count = 0;
+        let mut total_amount = 0;
+        for address in &addresses {
+            for output_response in self.consolidation_inputs(address).await? {
+                input_count += 1;
+                total_amount += Output::try_from_dto(&output_response.output, token_supply)?.amount();
+            }
+        }
+
+        Ok(ConsolidationPlan {
+            input_count,
+            total_amount,
+            target_address,
+        })
+    }
+
+    // Get outputs that can be controlled by the address without further unlock constraints
+    async fn consolidation_inputs(&self, address: &str) -> Result<Vec<OutputWithMetadataResponse>> {
+        let output_ids_response = self
+            .basic_output_ids(vec![
+                QueryParameter::Address(address.to_string()),
+                QueryParameter::HasExpiration(false),
+                QueryParameter::HasTimelock(false),
+                QueryParameter::HasStorageDepositReturn(false),
+            ])
+            .await?;
+
+        self.get_outputs(output_ids_response.items).await
+    }
+
+    /// Function to split an output into `count` basic outputs of `amount_each`, which are sent to the provided
+    /// addresses in turn. The source output has to be a basic output that is only locked to its address and
+    /// `amount_each` has to cover the minimum storage deposit of the created outputs. The rest of the amount and the
+    /// native tokens of the source output are sent to a remainder output, whose minimum storage deposit has to be
+    /// covered by the source output as well. Returns the block with the transaction.
+    pub async fn fan_out(
+        &self,
+        secret_manager: &SecretManager,
+        source_output: OutputId,
+        count: usize,
+        amount_each: u64,
+        to_addresses: &[String],
+    ) -> Result<Block> {
+        if count == 0 {
+            return Err(Error::MissingParameter("count"));
+        }
+        if to_addresses.is_empty() {
+            return Err(Error::MissingParameter("to_addresses"));
+        }
+
+        let token_supply = self.get_token_supply().await?;
+        let rent_structure = self.get_rent_structure().await?;
+        let output_response = self.get_output(&source_output).await?;
+        let source = Output::try_from_dto(&output_response.output, token_supply)?;
+        // Basic outputs always have an address unlock condition, so it has to be the only one.
+        match &source {
+            Output::Basic(basic_output) if basic_output.unlock_conditions().len() == 1 => {}
+            _ => {
+                return Err(Error::Output(
+                    "fan out source has to be a basic output only locked to its address",
+                ))
+            }
+        }
+        let total_amount = amount_each
+            .checked_mul(count as u64)
+            .ok_or(iota_types::block::Error::CreatedAmountOverflow)?;
+
+        // The rest of the amount and the native tokens are sent to a remainder output, which needs at least the
+        // minimum storage deposit. All address kinds have the same length, so the address doesn't change it.
+        let native_tokens = source
+            .native_tokens()
+            .map(|native_tokens| native_tokens.to_vec())
+            .unwrap_or_default();
+        let required_amount = if source.amount() != total_amount || !native_tokens.is_empty() {
+            let remainder = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure.clone())?
+                .with_native_tokens(native_tokens)
+                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
+                    Address::try_from_bech32(&to_addresses[0])?.1,
+                )))
+                .finish_output(token_supply)?;
+            total_amount
+                .checked_add(remainder.amount())
+                .ok_or(iota_types::block::Error::CreatedAmountOverflow)?
+        } else {
+            total_amount
+        };
+
+        let mut outputs = Vec::with_capacity(count);
+        for address in to_addresses.iter().cycle().take(count) {
+            outputs.push(
+                BasicOutputBuilder::new_with_amount(amount_each)?
+                    .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
+                        Address::try_from_bech32(address)?.1,
+                    )))
+                    .finish_output(token_supply)?,
+            );
+        }
+        // All created outputs have the same storage deposit, as all address kinds have the same length.
+        outputs[0].verify_storage_deposit(rent_structure, token_supply)?;
+
+        if source.amount() < required_amount {
+            return Err(InputSelectionError::InsufficientAmount {
+                found: source.amount(),
+                required: required_amount,
+            }
+            .into());
+        }
+
+        self.block()
+            .with_secret_manager(secret_manager)
+            .with_input(UtxoInput::from(source_output))?
+            .with_outputs(outputs)?
+            .finish()
+            .await
+    }
 }
@@ -1,33 +1,73 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    time::Duration,
+};
 
+use futures::StreamExt;
 use iota_types::{
     api::core::{dto::LedgerInclusionStateDto, response::OutputWithMetadataResponse},
     block::{
+        address::{Address, AliasAddress},
         input::{Input, UtxoInput, INPUT_COUNT_MAX},
-        output::{Output, OutputId},
+        output::{
+            unlock_condition::UnlockConditions, AliasId, FoundryId, NftId, Output, OutputId, TokenId, TokenScheme,
+        },
         parent::Parents,
         payload::{
             transaction::{TransactionEssence, TransactionId},
-            Payload,
+            Payload, TaggedDataPayload,
         },
+        semantic::ConflictReason,
         Block, BlockId,
     },
 };
+use primitive_types::U256;
 
 use crate::{
-    api::{input_selection::Error as InputSelectionError, ClientBlockBuilder, GetAddressesBuilder},
+    api::{
+        input_selection::{is_alias_transition, Error as InputSelectionError},
+        transaction::verify_unsigned_semantic,
+        BalanceScan, ClientBlockBuilder, DryRunReport, GetAddressesBuilder, NftHistory, PreparedTransactionData,
+        RetryCancel, ScanCursor, ADDRESS_GAP_RANGE,
+    },
     constants::{
         DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL, DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT, FIVE_MINUTES_IN_SECONDS,
+        MAX_PARALLEL_API_REQUESTS, RETRY_UNTIL_INCLUDED_CANCEL_CHECK_INTERVAL,
     },
     error::{Error, Result},
     node_api::indexer::query_parameters::QueryParameter,
-    secret::SecretManager,
+    secret::{types::InputSigningData, SecretManager},
     unix_timestamp_now, Client,
 };
 
+// Page size of the ledger updates requested from a permanode.
+const LEDGER_UPDATES_PAGE_SIZE: usize = 100;
+
+// A page of the ledger updates of an address, as returned by the explorer API of a permanode.
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerUpdatesByAddressResponse {
+    items: Vec<LedgerUpdateByAddress>,
+    cursor: Option<String>,
+}
+
+// An output of an address got created or spent, the other fields are skipped when deserializing.
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerUpdateByAddress {
+    #[serde(rename = "isSpent")]
+    is_spent: bool,
+}
+
+// The basic output ids of the public and internal address of an address index, found by an address scan.
+struct ScannedAddressIndex {
+    index: u32,
+    public: (String, Vec<OutputId>),
+    internal: (String, Vec<OutputId>),
+}
+
 impl Client {
     /// Get the inputs of a transaction for the given transaction id.
     pub async fn inputs_from_transaction_id(
@@ -58,6 +98,118 @@ impl Client {
         self.get_outputs(input_ids).await
     }
 
+    /// Resolve the inputs of the transaction contained in the block with the given block id to the outputs they
+    /// consume. Errors if the block doesn't contain a transaction or if an input can't be found anymore, because it
+    /// was pruned by the node.
+    pub async fn resolve_transaction_inputs(&self, block_id: &BlockId) -> Result<Vec<OutputWithMetadataResponse>> {
+        let block = self.get_block(block_id).await?;
+
+        let inputs = match block.payload() {
+            Some(Payload::Transaction(t)) => match t.essence() {
+                TransactionEssence::Regular(e) => e.inputs(),
+            },
+            _ => return Err(Error::MissingTransactionPayload(block_id.to_string())),
+        };
+
+        let mut outputs = Vec::with_capacity(inputs.len());
+
+        for input in inputs.iter() {
+            if let Input::Utxo(input) = input {
+                match self.get_output(input.output_id()).await {
+                    Ok(output_response) => outputs.push(output_response),
+                    Err(Error::NotFound(_)) => return Err(Error::InputPruned(input.output_id().to_string())),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Checks a prepared transaction without posting it: the semantic validation is done locally, with the addresses
+    /// the inputs are locked to considered unlocked since the transaction isn't signed yet, and the node is asked
+    /// whether the inputs are still unspent. Not covered are the signatures themselves, whether the signed transaction
+    /// still fits into a block, and conflicts with other transactions that aren't confirmed yet.
+    pub async fn dry_run(&self, prepared_transaction_data: &PreparedTransactionData) -> Result<DryRunReport> {
+        let current_time = self.get_time_checked().await?;
+        let conflict = verify_unsigned_semantic(prepared_transaction_data, current_time)?;
+
+        // Ask for the metadata of the inputs, with a limited amount of requests at once.
+        let metadata_responses = futures::stream::iter(&prepared_transaction_data.inputs_data)
+            .map(|input| async move { (*input.output_id(), self.get_output_metadata(input.output_id()).await) })
+            .buffered(MAX_PARALLEL_API_REQUESTS)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut unspendable_inputs = Vec::new();
+
+        for (output_id, metadata_response) in metadata_responses {
+            match metadata_response {
+                Ok(metadata) => {
+                    if metadata.is_spent {
+                        unspendable_inputs.push((output_id, ConflictReason::InputUtxoAlreadySpent));
+                    }
+                }
+                Err(Error::NotFound(_)) => {
+                    unspendable_inputs.push((output_id, ConflictReason::InputUtxoNotFound));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(DryRunReport {
+            conflict,
+            unspendable_inputs,
+        })
+    }
+
+    /// Computes the change of the balance of the given bech32 addresses from milestone `from_milestone` (exclusive) to
+    /// `to_milestone` (inclusive), from the UTXO changes of these milestones. Only outputs that are owned through an
+    /// address unlock condition are taken into account. Errors if the data of a milestone in the range was pruned.
+    pub async fn balance_delta(&self, addresses: &[String], from_milestone: u32, to_milestone: u32) -> Result<i128> {
+        let addresses = addresses
+            .iter()
+            .map(|address| Ok(Address::try_from_bech32(address)?.1))
+            .collect::<Result<HashSet<Address>>>()?;
+        let token_supply = self.get_token_supply().await?;
+        let mut delta: i128 = 0;
+
+        for index in from_milestone.saturating_add(1)..=to_milestone {
+            let utxo_changes = match self.get_utxo_changes_by_index(index).await {
+                Ok(utxo_changes) => utxo_changes,
+                Err(Error::NotFound(_)) => return Err(Error::MilestonePruned(index)),
+                Err(e) => return Err(e),
+            };
+
+            for (output_ids, created) in [
+                (utxo_changes.created_outputs, true),
+                (utxo_changes.consumed_outputs, false),
+            ] {
+                let output_ids = output_ids
+                    .iter()
+                    .map(|output_id| Ok(OutputId::from_str(output_id)?))
+                    .collect::<Result<Vec<OutputId>>>()?;
+                let output_responses = match self.get_outputs(output_ids).await {
+                    Ok(output_responses) => output_responses,
+                    Err(Error::NotFound(_)) => return Err(Error::MilestonePruned(index)),
+                    Err(e) => return Err(e),
+                };
+
+                for output_response in output_responses {
+                    let output = Output::try_from_dto(&output_response.output, token_supply)?;
+                    if let Some(address) = output.unlock_conditions().and_then(UnlockConditions::address) {
+                        if addresses.contains(address.address()) {
+                            let amount = i128::from(output.amount());
+                            delta += if created { amount } else { -amount };
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(delta)
+    }
+
     /// A generic send function for easily sending transaction or tagged data blocks.
     pub fn block(&self) -> ClientBlockBuilder<'_> {
         ClientBlockBuilder::new(self)
@@ -68,24 +220,100 @@ impl Client {
         GetAddressesBuilder::new(secret_manager).with_client(self)
     }
 
-    /// Find all blocks by provided block IDs.
+    /// Find all blocks by provided block IDs. Duplicated block IDs are only requested once and blocks that can't be
+    /// found, for example because they were pruned, are skipped.
     pub async fn find_blocks(&self, block_ids: &[BlockId]) -> Result<Vec<Block>> {
-        let mut blocks = Vec::new();
-
         // Use a `HashSet` to prevent duplicate block_ids.
         let mut block_ids_to_query = HashSet::<BlockId>::new();
+        let block_ids = block_ids
+            .iter()
+            .filter(|block_id| block_ids_to_query.insert(**block_id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        // Use `get_block()` API to get the `Block`, with a limited amount of requests at once.
+        let responses: Vec<Result<Block>> = futures::stream::iter(block_ids)
+            .map(|block_id| async move { self.get_block(&block_id).await })
+            .buffered(MAX_PARALLEL_API_REQUESTS)
+            .collect()
+            .await;
+
+        let mut blocks = Vec::new();
+        for response in responses {
+            match response {
+                Ok(block) => blocks.push(block),
+                Err(Error::NotFound(url)) => log::debug!("[find_blocks] skipping block that wasn't found: {url}"),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Posts data which can be larger than a single tagged data payload allows, split into chunks in separate blocks
+    /// with the given tag. Each chunk starts with the id of the block with the next chunk, or a null id for the last
+    /// one, so the chunks are posted in reverse order. Returns the block ids in the order of the chunks, the data can
+    /// be reassembled from the first one with [`Client::read_data_chunked()`].
+    pub async fn send_data_chunked(&self, tag: &[u8], data: &[u8]) -> Result<Vec<BlockId>> {
+        let max_tag_length = *TaggedDataPayload::TAG_LENGTH_RANGE.end() as usize;
+        if tag.len() > max_tag_length {
+            return Err(Error::TagTooLong {
+                length: tag.len(),
+                max_length: max_tag_length,
+            });
+        }
+
+        // The space of the max block length that isn't needed for the parents, the payload fields and the chunk header.
+        let block_overhead = Block::LENGTH_MIN + (*Parents::COUNT_RANGE.end() as usize - 1) * BlockId::LENGTH;
+        let payload_overhead = std::mem::size_of::<u32>() + 1 + tag.len() + std::mem::size_of::<u32>();
+        let chunk_length = Block::LENGTH_MAX - block_overhead - payload_overhead - BlockId::LENGTH;
 
-        // Collect the `BlockId` in the HashSet.
-        for block_id in block_ids {
-            block_ids_to_query.insert(*block_id);
+        let mut chunks = data.chunks(chunk_length).collect::<Vec<_>>();
+        // Empty data is still posted, so there is a block to read it from.
+        if chunks.is_empty() {
+            chunks.push(data);
         }
 
-        // Use `get_block()` API to get the `Block`.
-        for block_id in block_ids_to_query {
+        let mut block_ids = Vec::with_capacity(chunks.len());
+        let mut next_block_id = BlockId::null();
+        for chunk in chunks.into_iter().rev() {
+            let mut chunk_data = Vec::with_capacity(BlockId::LENGTH + chunk.len());
+            chunk_data.extend_from_slice(next_block_id.as_ref());
+            chunk_data.extend_from_slice(chunk);
+
+            let block = self
+                .block()
+                .with_tag(tag.to_vec())
+                .with_data(chunk_data)
+                .finish()
+                .await?;
+            next_block_id = block.id();
+            block_ids.push(next_block_id);
+        }
+        block_ids.reverse();
+
+        Ok(block_ids)
+    }
+
+    /// Reassembles data posted with [`Client::send_data_chunked()`], starting from the block with the first chunk.
+    pub async fn read_data_chunked(&self, first_block_id: &BlockId) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        let mut block_id = *first_block_id;
+
+        loop {
             let block = self.get_block(&block_id).await?;
-            blocks.push(block);
+            let chunk = match block.payload() {
+                Some(Payload::TaggedData(payload)) if payload.data().len() >= BlockId::LENGTH => payload.data(),
+                _ => return Err(Error::InvalidDataChunk(block_id.to_string())),
+            };
+            let (next_block_id, chunk) = chunk.split_at(BlockId::LENGTH);
+            data.extend_from_slice(chunk);
+
+            block_id = BlockId::new(next_block_id.try_into().expect("the length was checked"));
+            if block_id == BlockId::null() {
+                return Ok(data);
+            }
         }
-        Ok(blocks)
     }
 
     /// Retries (promotes or reattaches) a block for provided block id. Block should only be
@@ -103,33 +331,45 @@ impl Client {
     }
 
     /// Retries (promotes or reattaches) a block for provided block id until it's included (referenced by a
-    /// milestone). Default interval is 5 seconds and max attempts is 40. Returns the included block at first position
-    /// and additional reattached blocks
+    /// milestone). Default interval is 1 second and max attempts is 40. Returns the included block at first position
+    /// and additional reattached blocks, together with the index of the milestone that referenced the included block
     pub async fn retry_until_included(
         &self,
         block_id: &BlockId,
         interval: Option<u64>,
         max_attempts: Option<u64>,
-    ) -> Result<Vec<(BlockId, Block)>> {
+    ) -> Result<(Vec<(BlockId, Block)>, u32)> {
+        let (blocks, milestone_index) = self
+            .retry_until_included_with_cancel(block_id, interval, max_attempts, &RetryCancel::new())
+            .await?;
+        // PANIC: the milestone index is only missing if the retrying got cancelled, which can't happen here.
+        Ok((blocks, milestone_index.expect("retrying wasn't cancelled")))
+    }
+
+    /// Like [`Client::retry_until_included()`], but it can be stopped with the given [`RetryCancel`]. If it got
+    /// cancelled, the blocks reattached so far are returned without a milestone index.
+    pub async fn retry_until_included_with_cancel(
+        &self,
+        block_id: &BlockId,
+        interval: Option<u64>,
+        max_attempts: Option<u64>,
+        cancel: &RetryCancel,
+    ) -> Result<(Vec<(BlockId, Block)>, Option<u32>)> {
         log::debug!("[retry_until_included]");
         // Attachments of the Block to check inclusion state
         let mut block_ids = vec![*block_id];
         // Reattached Blocks that get returned
         let mut blocks_with_id = Vec::new();
         for _ in 0..max_attempts.unwrap_or(DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT) {
-            #[cfg(target_family = "wasm")]
-            gloo_timers::future::TimeoutFuture::new(
-                (interval.unwrap_or(DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL) * 1000)
-                    .try_into()
-                    .unwrap(),
+            sleep_unless_cancelled(
+                Duration::from_secs(interval.unwrap_or(DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL)),
+                cancel,
             )
             .await;
-
-            #[cfg(not(target_family = "wasm"))]
-            tokio::time::sleep(std::time::Duration::from_secs(
-                interval.unwrap_or(DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL),
-            ))
-            .await;
+            if cancel.is_cancelled() {
+                log::debug!("[retry_until_included] cancelled");
+                return Ok((blocks_with_id, None));
+            }
 
             // Check inclusion state for each attachment
             let block_ids_len = block_ids.len();
@@ -139,16 +379,20 @@ impl Client {
                 if let Some(inclusion_state) = block_metadata.ledger_inclusion_state {
                     match inclusion_state {
                         LedgerInclusionStateDto::Included | LedgerInclusionStateDto::NoTransaction => {
+                            let milestone_index = block_metadata
+                                .referenced_by_milestone_index
+                                .ok_or(Error::UnexpectedApiResponse)?;
                             // if original block, request it so we can return it on first position
                             if block_id == block_id_ {
                                 let mut included_and_reattached_blocks =
                                     vec![(*block_id, self.get_block(block_id).await?)];
                                 included_and_reattached_blocks.extend(blocks_with_id);
-                                return Ok(included_and_reattached_blocks);
+                                return Ok((included_and_reattached_blocks, Some(milestone_index)));
                             } else {
-                                // Move included block to first position
-                                blocks_with_id.rotate_left(index);
-                                return Ok(blocks_with_id);
+                                // Move included block to first position, the original block is the first attachment
+                                // but not part of the reattached blocks
+                                blocks_with_id.rotate_left(index - 1);
+                                return Ok((blocks_with_id, Some(milestone_index)));
                             }
                         }
                         // only set it as conflicting here and don't return, because another reattached block could
@@ -175,9 +419,14 @@ impl Client {
                 let block = self.get_block(block_id).await?;
                 if let Some(Payload::Transaction(transaction_payload)) = block.payload() {
                     let included_block = self.get_included_block(&transaction_payload.id()).await?;
+                    let milestone_index = self
+                        .get_block_metadata(&included_block.id())
+                        .await?
+                        .referenced_by_milestone_index
+                        .ok_or(Error::UnexpectedApiResponse)?;
                     let mut included_and_reattached_blocks = vec![(included_block.id(), included_block)];
                     included_and_reattached_blocks.extend(blocks_with_id);
-                    return Ok(included_and_reattached_blocks);
+                    return Ok((included_and_reattached_blocks, Some(milestone_index)));
                 }
             }
         }
@@ -185,12 +434,18 @@ impl Client {
     }
 
     /// Function to find inputs from addresses for a provided amount (useful for offline signing), ignoring outputs with
-    /// additional unlock conditions
+    /// additional unlock conditions. The addresses are queried in order, the remaining ones are skipped as soon as the
+    /// outputs found so far cover the amount.
     pub async fn find_inputs(&self, addresses: Vec<String>, amount: u64) -> Result<Vec<UtxoInput>> {
         // Get outputs from node and select inputs
-        let mut available_outputs = Vec::new();
+        let mut basic_outputs = Vec::new();
+        let mut available_amount = 0;
+        let token_supply = self.get_token_supply().await?;
 
         for address in addresses {
+            if available_amount >= amount {
+                break;
+            }
             let output_ids_response = self
                 .basic_output_ids(vec![
                     QueryParameter::Address(address.to_string()),
@@ -200,21 +455,17 @@ impl Client {
                 ])
                 .await?;
 
-            available_outputs.extend(self.get_outputs(output_ids_response.items).await?);
-        }
-
-        let mut basic_outputs = Vec::new();
-        let token_supply = self.get_token_supply().await?;
-
-        for output_resp in available_outputs {
-            let amount = Output::try_from_dto(&output_resp.output, token_supply)?.amount();
-            basic_outputs.push((
-                UtxoInput::new(
-                    TransactionId::from_str(&output_resp.metadata.transaction_id)?,
-                    output_resp.metadata.output_index,
-                )?,
-                amount,
-            ));
+            for output_resp in self.get_outputs(output_ids_response.items).await? {
+                let amount = Output::try_from_dto(&output_resp.output, token_supply)?.amount();
+                available_amount += amount;
+                basic_outputs.push((
+                    UtxoInput::new(
+                        TransactionId::from_str(&output_resp.metadata.transaction_id)?,
+                        output_resp.metadata.output_index,
+                    )?,
+                    amount,
+                ));
+            }
         }
         basic_outputs.sort_by(|l, r| r.1.cmp(&l.1));
 
@@ -244,6 +495,210 @@ impl Client {
         Ok(selected_inputs)
     }
 
+    /// Sends the outputs only if the balance of the account is at least `min_balance`, otherwise
+    /// [`Error::InsufficientBalanceForCondition`] is returned and nothing is posted. The balance is the amount of the
+    /// outputs the automatic input selection finds on the account addresses, which are scanned once for both the check
+    /// and the selection of the inputs.
+    pub async fn send_if_balance_at_least(
+        &self,
+        secret_manager: &SecretManager,
+        account_index: u32,
+        min_balance: u64,
+        outputs: Vec<Output>,
+    ) -> Result<Block> {
+        self.block()
+            .with_secret_manager(secret_manager)
+            .with_account_index(account_index)
+            .with_min_balance(min_balance)
+            .with_outputs(outputs)?
+            .finish()
+            .await
+    }
+
+    /// Gets the ids of the basic outputs of the public and internal addresses of an account, starting at the cursor,
+    /// until [`ADDRESS_GAP_RANGE`] consecutive address indexes are empty. Use [`ScanCursor::new()`] to scan an account
+    /// from its first address. If the scan fails, [`Error::AddressScanInterrupted`] contains the output ids found so
+    /// far and the cursor to resume the scan from.
+    pub async fn account_output_ids(
+        &self,
+        secret_manager: &SecretManager,
+        mut cursor: ScanCursor,
+    ) -> Result<Vec<OutputId>> {
+        let mut scanned = Vec::new();
+        let mut result = Ok(true);
+
+        while matches!(result, Ok(true)) {
+            result = self
+                .scan_account_addresses(secret_manager, &mut cursor, ADDRESS_GAP_RANGE, &mut scanned)
+                .await;
+        }
+
+        let output_ids = scanned
+            .into_iter()
+            .flat_map(|address_index| address_index.public.1.into_iter().chain(address_index.internal.1))
+            .collect();
+
+        match result {
+            Ok(_) => Ok(output_ids),
+            Err(error) => Err(Error::AddressScanInterrupted {
+                cursor,
+                output_ids,
+                error: Box::new(error),
+            }),
+        }
+    }
+
+    // Scans the next batch of address indexes of an account for the basic output ids of their public and internal
+    // addresses, if fewer than `gap_limit` consecutive address indexes are empty. Returns false once the gap limit is
+    // reached. Advances the cursor only after an address index was added to `scanned`, so that it always points to the
+    // first address index that wasn't scanned.
+    async fn scan_account_addresses(
+        &self,
+        secret_manager: &SecretManager,
+        cursor: &mut ScanCursor,
+        gap_limit: u32,
+        scanned: &mut Vec<ScannedAddressIndex>,
+    ) -> Result<bool> {
+        if cursor.empty_count >= gap_limit {
+            return Ok(false);
+        }
+
+        let block_builder = self.block();
+        let addresses = self
+            .get_addresses(secret_manager)
+            .with_account_index(cursor.account_index)
+            .with_range(cursor.next_index..cursor.next_index + gap_limit - cursor.empty_count)
+            .get_all()
+            .await?;
+
+        for (public, internal) in addresses.public.into_iter().zip(addresses.internal) {
+            let public_output_ids = block_builder.basic_address_output_ids(public.clone()).await?;
+            let internal_output_ids = block_builder.basic_address_output_ids(internal.clone()).await?;
+
+            if public_output_ids.is_empty() && internal_output_ids.is_empty() {
+                cursor.empty_count += 1;
+            } else {
+                cursor.empty_count = 0;
+            }
+            scanned.push(ScannedAddressIndex {
+                index: cursor.next_index,
+                public: (public, public_output_ids),
+                internal: (internal, internal_output_ids),
+            });
+            cursor.next_index += 1;
+        }
+
+        Ok(true)
+    }
+
+    /// Scans the public and internal addresses of an account for their balance, in batches of address indexes, until
+    /// `gap_limit` consecutive address indexes are empty. `on_balance` is called for each address with a balance as
+    /// soon as its batch is scanned. The balance of an address is the amount of its basic outputs that can be used as
+    /// inputs by the automatic input selection.
+    pub async fn scan_balances(
+        &self,
+        secret_manager: &SecretManager,
+        account_index: u32,
+        gap_limit: u32,
+        mut on_balance: impl FnMut(&str, u64),
+    ) -> Result<BalanceScan> {
+        let token_supply = self.get_token_supply().await?;
+        let mut cursor = ScanCursor::new(account_index);
+        let mut scanned = Vec::new();
+        let mut scan = BalanceScan {
+            total_balance: 0,
+            highest_used_index: None,
+        };
+
+        while self
+            .scan_account_addresses(secret_manager, &mut cursor, gap_limit, &mut scanned)
+            .await?
+        {
+            for address_index in std::mem::take(&mut scanned) {
+                for (address, output_ids) in [address_index.public, address_index.internal] {
+                    if output_ids.is_empty() {
+                        continue;
+                    }
+                    let balance = self.outputs_amount(output_ids, token_supply).await?;
+                    on_balance(&address, balance);
+                    scan.total_balance += balance;
+                    scan.highest_used_index.replace(address_index.index);
+                }
+            }
+        }
+
+        Ok(scan)
+    }
+
+    // Sums the amount of the outputs.
+    async fn outputs_amount(&self, output_ids: Vec<OutputId>, token_supply: u64) -> Result<u64> {
+        let mut amount = 0;
+        for output_response in self.get_outputs(output_ids).await? {
+            amount += Output::try_from_dto(&output_response.output, token_supply)?.amount();
+        }
+
+        Ok(amount)
+    }
+
+    /// Checks whether an output belongs to the account, without scanning for its other outputs. That is the case if
+    /// one of the first `gap_limit` public or internal addresses of the account is the address that can unlock the
+    /// output at the current time, or the governor address if it's an alias output.
+    pub async fn is_output_owned(
+        &self,
+        secret_manager: &SecretManager,
+        account_index: u32,
+        output_id: &OutputId,
+        gap_limit: u32,
+    ) -> Result<bool> {
+        let output_response = self.get_output(output_id).await?;
+        let output = Output::try_from_dto(&output_response.output, self.get_token_supply().await?)?;
+        let current_time = self.get_time_checked().await?;
+
+        let mut owner_addresses = vec![output.required_and_unlocked_address(current_time, output_id, None)?.0];
+        if let Output::Alias(alias_output) = &output {
+            owner_addresses.push(*alias_output.governor_address());
+        }
+
+        let addresses = self
+            .get_addresses(secret_manager)
+            .with_account_index(account_index)
+            .with_range(0..gap_limit)
+            .get_all_raw()
+            .await?;
+
+        Ok(addresses
+            .public
+            .iter()
+            .chain(&addresses.internal)
+            .any(|address| owner_addresses.contains(address)))
+    }
+
+    /// Gets the distinct addresses that are required to unlock the inputs of a transaction with the given outputs, so
+    /// one can check that all needed keys are available before signing. Alias and nft addresses are only included if
+    /// the alias or nft that unlocks them isn't one of the inputs.
+    pub async fn required_unlock_addresses(
+        &self,
+        inputs: &[InputSigningData],
+        outputs: &[Output],
+    ) -> Result<HashSet<Address>> {
+        let current_time = self.get_time_checked().await?;
+        let mut required_addresses = HashSet::new();
+        let mut unlocked_addresses = HashSet::new();
+
+        for input in inputs {
+            let alias_transition = is_alias_transition(input, outputs).map(|transition| transition.0);
+            let (required_address, unlocked_address) =
+                input
+                    .output
+                    .required_and_unlocked_address(current_time, input.output_id(), alias_transition)?;
+
+            required_addresses.insert(required_address);
+            unlocked_addresses.extend(unlocked_address);
+        }
+
+        Ok(required_addresses.difference(&unlocked_addresses).copied().collect())
+    }
+
     /// Find all outputs based on the requests criteria. This method will try to query multiple nodes if
     /// the request amount exceeds individual node limit.
     pub async fn find_outputs(
@@ -272,6 +727,175 @@ impl Client {
         Ok(output_responses.clone())
     }
 
+    /// Checks whether an output of the given bech32 address has ever been spent, to avoid reusing it. The indexer only
+    /// knows unspent outputs, so this needs a permanode providing the ledger updates of the explorer API, otherwise
+    /// [`Error::FeatureNotSupportedByNode`] is returned.
+    pub async fn address_has_spent_outputs(&self, address: &str) -> Result<bool> {
+        Address::try_from_bech32(address)?;
+        let path = format!("api/explorer/v2/ledger/updates/by-address/{address}");
+        let mut cursor = None;
+
+        loop {
+            let mut query = format!("pageSize={LEDGER_UPDATES_PAGE_SIZE}");
+            if let Some(cursor) = &cursor {
+                query.push_str(&format!("&cursor={cursor}"));
+            }
+            let page = match self
+                .node_manager
+                .get_request::<LedgerUpdatesByAddressResponse>(&path, Some(&query), self.get_timeout(), false, true)
+                .await
+            {
+                Ok(page) => page,
+                Err(Error::NotFound(_)) => {
+                    return Err(Error::FeatureNotSupportedByNode(
+                        "ledger updates by address".to_string(),
+                    ));
+                }
+                Err(e) => return Err(e),
+            };
+
+            if page.items.iter().any(|update| update.is_spent) {
+                return Ok(true);
+            }
+            match page.cursor {
+                Some(next_cursor) => cursor.replace(next_cursor),
+                None => return Ok(false),
+            };
+        }
+    }
+
+    /// Get the basic outputs that can be unlocked by each of the given bech32 addresses at the current time, without
+    /// storage deposit return unlock condition. Duplicated addresses and outputs are only requested once and the
+    /// requests are sent in parallel.
+    pub async fn outputs_for_addresses(
+        &self,
+        addresses: &[String],
+    ) -> Result<HashMap<String, Vec<OutputWithMetadataResponse>>> {
+        let block_builder = self.block();
+        let mut unique_addresses = HashSet::new();
+        let addresses = addresses
+            .iter()
+            .filter(|address| unique_addresses.insert(*address))
+            .collect::<Vec<_>>();
+
+        // The indexer only allows to filter by a single address, so one query per address is needed.
+        let address_output_ids = futures::stream::iter(addresses)
+            .map(|address| {
+                let block_builder = &block_builder;
+                async move {
+                    let output_ids = block_builder.basic_address_output_ids(address.clone()).await?;
+                    Ok((address.clone(), output_ids))
+                }
+            })
+            .buffered(MAX_PARALLEL_API_REQUESTS)
+            .collect::<Vec<Result<(String, Vec<OutputId>)>>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        // An output can be found for multiple addresses through its expiration unlock condition.
+        let mut unique_output_ids = HashSet::new();
+        let output_ids = address_output_ids
+            .iter()
+            .flat_map(|(_, output_ids)| output_ids)
+            .filter(|output_id| unique_output_ids.insert(**output_id))
+            .copied()
+            .collect::<Vec<_>>();
+        let outputs = output_ids
+            .iter()
+            .copied()
+            .zip(self.get_outputs(output_ids.clone()).await?)
+            .collect::<HashMap<_, _>>();
+
+        Ok(address_output_ids
+            .into_iter()
+            .map(|(address, output_ids)| {
+                let address_outputs = output_ids
+                    .iter()
+                    .filter_map(|output_id| outputs.get(output_id).cloned())
+                    .collect();
+                (address, address_outputs)
+            })
+            .collect())
+    }
+
+    /// Get the output ids of all foundries controlled by the alias with the given id.
+    pub async fn foundries_of_alias(&self, alias_id: AliasId) -> Result<Vec<OutputId>> {
+        let alias_address = Address::Alias(AliasAddress::new(alias_id)).to_bech32(self.get_bech32_hrp().await?);
+
+        Ok(self
+            .foundry_output_ids(vec![QueryParameter::AliasAddress(alias_address)])
+            .await?
+            .items)
+    }
+
+    /// Get the circulating supply of a native token, the minted minus the melted tokens of the foundry controlling it.
+    pub async fn native_token_supply(&self, token_id: TokenId) -> Result<U256> {
+        let output_id = self.foundry_output_id(FoundryId::from(token_id)).await?;
+        let output_response = self.get_output(&output_id).await?;
+
+        match Output::try_from_dto(&output_response.output, self.get_token_supply().await?)? {
+            Output::Foundry(foundry_output) => {
+                let TokenScheme::Simple(token_scheme) = foundry_output.token_scheme();
+                Ok(token_scheme.circulating_supply())
+            }
+            _ => Err(Error::UnexpectedApiResponse),
+        }
+    }
+
+    /// Get the output history of an NFT, by walking backward from its current output through the transactions that
+    /// created the outputs, until the minting output is reached. If the data of older transactions or outputs was
+    /// pruned by the node, the history is truncated and marked as incomplete.
+    pub async fn nft_history(&self, nft_id: NftId) -> Result<NftHistory> {
+        let token_supply = self.get_token_supply().await?;
+        let mut output_id = self.nft_output_id(nft_id).await?;
+        let mut output_ids = vec![output_id];
+
+        let complete = 'history: loop {
+            // The NFT id is derived from the id of the minting output.
+            if NftId::from(&output_id) == nft_id {
+                break true;
+            }
+
+            let block = match self.get_included_block(output_id.transaction_id()).await {
+                Ok(block) => block,
+                Err(Error::NotFound(_)) => break false,
+                Err(e) => return Err(e),
+            };
+            let inputs = match block.payload() {
+                Some(Payload::Transaction(t)) => match t.essence() {
+                    TransactionEssence::Regular(e) => e.inputs(),
+                },
+                _ => return Err(Error::MissingTransactionPayload(block.id().to_string())),
+            };
+
+            // The previous output of the NFT is the input that holds it.
+            let mut previous_output_id = None;
+            for input in inputs.iter() {
+                if let Input::Utxo(input) = input {
+                    let output_response = match self.get_output(input.output_id()).await {
+                        Ok(output_response) => output_response,
+                        Err(Error::NotFound(_)) => break 'history false,
+                        Err(e) => return Err(e),
+                    };
+                    if let Output::Nft(nft_output) = Output::try_from_dto(&output_response.output, token_supply)? {
+                        if nft_output.nft_id_non_null(input.output_id()) == nft_id {
+                            previous_output_id.replace(*input.output_id());
+                            break;
+                        }
+                    }
+                }
+            }
+
+            output_id = previous_output_id.ok_or(Error::UnexpectedApiResponse)?;
+            output_ids.push(output_id);
+        };
+
+        output_ids.reverse();
+
+        Ok(NftHistory { output_ids, complete })
+    }
+
     /// Reattaches blocks for provided block id. Blocks can be reattached only if they are valid and haven't been
     /// confirmed for a while.
     pub async fn reattach(&self, block_id: &BlockId) -> Result<(BlockId, Block)> {
@@ -290,7 +914,7 @@ impl Client {
         let reattach_block = self.finish_block_builder(None, block.payload().cloned()).await?;
 
         // Post the modified
-        let block_id = self.post_block_raw(&reattach_block).await?;
+        let block_id = self.submit_block(&reattach_block).await?;
         // Get block if we use remote Pow, because the node will change parents and nonce
         let block = if self.get_local_pow() {
             reattach_block
@@ -300,6 +924,16 @@ impl Client {
         Ok((block_id, block))
     }
 
+    /// Reattaches multiple blocks, with a limited amount of reattachments at once. The results are in the order of the
+    /// provided block ids, blocks that don't need to be reattached result in [`Error::NoNeedPromoteOrReattach`].
+    pub async fn reattach_many(&self, block_ids: &[BlockId]) -> Vec<Result<(BlockId, Block)>> {
+        futures::stream::iter(block_ids)
+            .map(|block_id| self.reattach(block_id))
+            .buffered(MAX_PARALLEL_API_REQUESTS)
+            .collect()
+            .await
+    }
+
     /// Promotes a block. The method should validate if a promotion is necessary through get_block. If not, the
     /// method should error out and should not allow unnecessary promotions.
     pub async fn promote(&self, block_id: &BlockId) -> Result<(BlockId, Block)> {
@@ -313,15 +947,38 @@ impl Client {
 
     /// Promote a block without checking if it should be promoted
     pub async fn promote_unchecked(&self, block_id: &BlockId) -> Result<(BlockId, Block)> {
-        // Create a new block (zero value block) for which one tip would be the actual block.
-        let mut tips = self.get_tips().await?;
-        if let Some(tip) = tips.first_mut() {
-            *tip = *block_id;
+        self.promote_with_parents(block_id, None).await
+    }
+
+    /// Promote a block without checking if it should be promoted, the reference tip is pinned as additional parent of
+    /// the promotion block. Errors if the reference isn't a tip of the node.
+    pub async fn promote_with_reference(&self, block_id: &BlockId, reference: &BlockId) -> Result<(BlockId, Block)> {
+        self.promote_with_parents(block_id, Some(reference)).await
+    }
+
+    async fn promote_with_parents(&self, block_id: &BlockId, reference: Option<&BlockId>) -> Result<(BlockId, Block)> {
+        let tips = self.get_tips().await?;
+        if let Some(reference) = reference {
+            if !tips.contains(reference) {
+                return Err(Error::ReferenceNotTip(reference.to_string()));
+            }
         }
 
-        let promote_block = self.finish_block_builder(Some(Parents::new(tips)?), None).await?;
+        // Create a new block (zero value block) for which one parent would be the actual block. Tips that are already
+        // a parent are skipped, so they don't take away the place of another tip.
+        let mut parents = vec![*block_id];
+        for tip in reference.into_iter().copied().chain(tips) {
+            if parents.len() == *Parents::COUNT_RANGE.end() as usize {
+                break;
+            }
+            if !parents.contains(&tip) {
+                parents.push(tip);
+            }
+        }
 
-        let block_id = self.post_block_raw(&promote_block).await?;
+        let promote_block = self.finish_block_builder(Some(Parents::new(parents)?), None).await?;
+
+        let block_id = self.submit_block(&promote_block).await?;
         // Get block if we use remote Pow, because the node will change parents and nonce.
         let block = if self.get_local_pow() {
             promote_block
@@ -333,12 +990,12 @@ impl Client {
 
     /// Returns the local time checked with the timestamp of the latest milestone, if the difference is larger than 5
     /// minutes an error is returned to prevent locking outputs by accident for a wrong time.
+    /// With multiple synced nodes, the median of their latest milestone timestamps is used, so a single node with a
+    /// wrong time can't affect the check. For an even amount of nodes, the lower of the two middle timestamps is used.
     pub async fn get_time_checked(&self) -> Result<u32> {
         let current_time = unix_timestamp_now();
 
-        let network_info = self.get_network_info().await?;
-
-        if let Some(latest_ms_timestamp) = network_info.latest_milestone_timestamp {
+        if let Some(latest_ms_timestamp) = self.get_median_milestone_timestamp().await? {
             // Check the local time is in the range of +-5 minutes of the node to prevent locking funds by accident
             if !(latest_ms_timestamp - FIVE_MINUTES_IN_SECONDS..latest_ms_timestamp + FIVE_MINUTES_IN_SECONDS)
                 .contains(&current_time)
@@ -352,4 +1009,50 @@ impl Client {
 
         Ok(current_time)
     }
+
+    // Returns the median of the latest milestone timestamps of all healthy nodes, as cached by the node syncing, or the
+    // one of the network info if fewer than two nodes are synced.
+    async fn get_median_milestone_timestamp(&self) -> Result<Option<u32>> {
+        let mut timestamps = self
+            .node_manager
+            .healthy_nodes
+            .read()
+            .map_err(|_| crate::Error::PoisonError)?
+            .iter()
+            .filter(|(node, _)| !node.disabled)
+            .filter_map(|(_, info)| info.status.latest_milestone.timestamp)
+            .collect::<Vec<u32>>();
+
+        if timestamps.len() < 2 {
+            return Ok(self.get_network_info().await?.latest_milestone_timestamp);
+        }
+        timestamps.sort_unstable();
+
+        let spread = timestamps[timestamps.len() - 1] - timestamps[0];
+        let tolerance = self.get_node_clock_skew_tolerance().as_secs() as u32;
+        if spread > tolerance {
+            return Err(Error::NodeClockSkew { spread, tolerance });
+        }
+
+        // The lower middle timestamp for an even amount, so two nodes can't move the check ahead of the slower one.
+        Ok(Some(timestamps[(timestamps.len() - 1) / 2]))
+    }
+}
+
+// Sleeps for the given duration in small steps, so it returns early once the retrying got cancelled.
+async fn sleep_unless_cancelled(duration: Duration, cancel: &RetryCancel) {
+    let start = instant::Instant::now();
+    while !cancel.is_cancelled() {
+        let remaining = duration.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            break;
+        }
+        let step = remaining.min(RETRY_UNTIL_INCLUDED_CANCEL_CHECK_INTERVAL);
+
+        #[cfg(target_family = "wasm")]
+        gloo_timers::future::TimeoutFuture::new(step.as_millis().try_into().unwrap()).await;
+
+        #[cfg(not(target_family = "wasm"))]
+        tokio::time::sleep(step).await;
+    }
 }
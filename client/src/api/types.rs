@@ -1,18 +1,27 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
 use iota_types::block::{
     address::{dto::AddressDto, Address},
-    output::{dto::OutputDto, Output},
+    input::Input,
+    output::{dto::OutputDto, Output, OutputId},
     payload::{
+        milestone::MilestoneId,
         transaction::{
             dto::{TransactionEssenceDto, TransactionPayloadDto},
-            TransactionEssence,
+            TransactionEssence, TransactionId,
         },
-        TransactionPayload,
+        Payload, TransactionPayload,
     },
     protocol::ProtocolParameters,
-    DtoError,
+    semantic::ConflictReason,
+    unlock::Unlock,
+    Block, BlockId, DtoError,
 };
 
 use crate::{
@@ -165,6 +174,106 @@ impl SignedTransactionData {
     }
 }
 
+/// Result of a dry run of a prepared transaction
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// Conflict found by the local semantic checks, [`ConflictReason::None`] if there was none
+    pub conflict: ConflictReason,
+    /// Inputs that can't be spent anymore, because the node knows them as spent or doesn't know them at all
+    #[serde(rename = "unspendableInputs")]
+    pub unspendable_inputs: Vec<(OutputId, ConflictReason)>,
+}
+
+impl DryRunReport {
+    /// Returns whether the transaction is expected to be accepted, once signed.
+    pub fn is_valid(&self) -> bool {
+        self.conflict == ConflictReason::None && self.unspendable_inputs.is_empty()
+    }
+}
+
+/// Output history of an NFT, as far as it's still known by the node
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NftHistory {
+    /// Ids of the outputs of the NFT, from the oldest known one to the current one
+    #[serde(rename = "outputIds")]
+    pub output_ids: Vec<OutputId>,
+    /// Whether the history goes back to the minting output, false if older outputs were pruned
+    pub complete: bool,
+}
+
+/// Position of an address scan of an account, to resume the scan where it was interrupted
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScanCursor {
+    /// The index of the scanned account
+    #[serde(rename = "accountIndex")]
+    pub account_index: u32,
+    /// The address index the scan continues with
+    #[serde(rename = "nextIndex")]
+    pub next_index: u32,
+    /// The amount of consecutive empty address indexes right before `next_index`
+    #[serde(rename = "emptyCount")]
+    pub empty_count: u32,
+}
+
+impl ScanCursor {
+    /// Creates a cursor to scan an account from its first address.
+    pub fn new(account_index: u32) -> Self {
+        Self {
+            account_index,
+            next_index: 0,
+            empty_count: 0,
+        }
+    }
+}
+
+/// Result of a balance scan of an account, see [`Client::scan_balances()`](crate::Client::scan_balances)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BalanceScan {
+    /// The sum of the balances of the scanned addresses
+    #[serde(rename = "totalBalance")]
+    pub total_balance: u64,
+    /// The highest address index with a balance on its public or internal address, if any
+    #[serde(rename = "highestUsedIndex")]
+    pub highest_used_index: Option<u32>,
+}
+
+/// Preview of a consolidation of funds, see [`Client::preview_consolidation()`](crate::Client::preview_consolidation)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationPlan {
+    /// The amount of outputs that would be consolidated
+    #[serde(rename = "inputCount")]
+    pub input_count: usize,
+    /// The amount of the resulting output
+    #[serde(rename = "totalAmount")]
+    pub total_amount: u64,
+    /// The address the funds would be consolidated to
+    #[serde(rename = "targetAddress")]
+    pub target_address: String,
+}
+
+/// Whether an address can unlock an output, see [`Client::can_unlock()`](crate::Client::can_unlock)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputUnlockability {
+    /// The address can't unlock the output, or the output is still timelocked
+    Locked,
+    /// The address can unlock the output
+    Unlockable,
+    /// The address can unlock the output, but a storage deposit return output has to be created
+    UnlockableWithReturn {
+        /// The address the storage deposit has to be returned to
+        return_address: Address,
+        /// The amount that has to be returned
+        amount: u64,
+    },
+}
+
+impl OutputUnlockability {
+    /// Returns whether the output can be unlocked, with or without a storage deposit return.
+    pub fn is_unlockable(&self) -> bool {
+        !matches!(self, Self::Locked)
+    }
+}
+
 /// Data for a remainder output, used for ledger nano
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RemainderData {
@@ -232,3 +341,134 @@ pub struct Bech32Addresses {
     /// Internal/change addresses <https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#change>
     pub internal: Vec<String>,
 }
+
+/// A block decoded from its bytes, with the content of its payload exposed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodedBlock {
+    /// The id of the block.
+    pub block_id: BlockId,
+    /// The protocol version of the block.
+    pub protocol_version: u8,
+    /// The parents of the block.
+    pub parents: Vec<BlockId>,
+    /// The decoded payload of the block, if any.
+    pub payload: Option<DecodedPayload>,
+    /// The nonce of the block.
+    pub nonce: u64,
+}
+
+/// The decoded payload of a [`DecodedBlock`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodedPayload {
+    /// A tagged data payload.
+    TaggedData {
+        /// The tag of the payload.
+        tag: Vec<u8>,
+        /// The data of the payload.
+        data: Vec<u8>,
+    },
+    /// A transaction payload.
+    Transaction {
+        /// The id of the transaction.
+        transaction_id: TransactionId,
+        /// The network id of the transaction.
+        network_id: u64,
+        /// The inputs of the transaction.
+        inputs: Vec<Input>,
+        /// The outputs of the transaction.
+        outputs: Vec<Output>,
+        /// The tagged data payload embedded in the transaction, if any.
+        tagged_data: Option<(Vec<u8>, Vec<u8>)>,
+        /// The unlocks of the transaction.
+        unlocks: Vec<Unlock>,
+    },
+    /// A milestone payload.
+    Milestone {
+        /// The id of the milestone.
+        milestone_id: MilestoneId,
+        /// The index of the milestone.
+        index: u32,
+        /// The timestamp of the milestone.
+        timestamp: u32,
+        /// The id of the previous milestone.
+        previous_milestone_id: MilestoneId,
+        /// The metadata of the milestone.
+        metadata: Vec<u8>,
+    },
+    /// A treasury transaction payload.
+    TreasuryTransaction {
+        /// The id of the milestone whose treasury output is consumed.
+        input_milestone_id: MilestoneId,
+        /// The amount of the new treasury output.
+        output_amount: u64,
+    },
+}
+
+impl From<&Block> for DecodedBlock {
+    fn from(block: &Block) -> Self {
+        let payload = block.payload().map(|payload| match payload {
+            Payload::TaggedData(tagged_data) => DecodedPayload::TaggedData {
+                tag: tagged_data.tag().to_vec(),
+                data: tagged_data.data().to_vec(),
+            },
+            Payload::Transaction(transaction) => {
+                let TransactionEssence::Regular(essence) = transaction.essence();
+                DecodedPayload::Transaction {
+                    transaction_id: transaction.id(),
+                    network_id: essence.network_id(),
+                    inputs: essence.inputs().to_vec(),
+                    outputs: essence.outputs().to_vec(),
+                    tagged_data: match essence.payload() {
+                        Some(Payload::TaggedData(tagged_data)) => {
+                            Some((tagged_data.tag().to_vec(), tagged_data.data().to_vec()))
+                        }
+                        _ => None,
+                    },
+                    unlocks: transaction.unlocks().to_vec(),
+                }
+            }
+            Payload::Milestone(milestone) => DecodedPayload::Milestone {
+                milestone_id: milestone.id(),
+                index: *milestone.essence().index(),
+                timestamp: milestone.essence().timestamp(),
+                previous_milestone_id: *milestone.essence().previous_milestone_id(),
+                metadata: milestone.essence().metadata().to_vec(),
+            },
+            Payload::TreasuryTransaction(treasury_transaction) => DecodedPayload::TreasuryTransaction {
+                input_milestone_id: *treasury_transaction.input().milestone_id(),
+                output_amount: treasury_transaction.output().amount(),
+            },
+        });
+
+        Self {
+            block_id: block.id(),
+            protocol_version: block.protocol_version(),
+            parents: block.parents().to_vec(),
+            payload,
+            nonce: block.nonce(),
+        }
+    }
+}
+
+/// Handle to cancel a [`Client::retry_until_included_with_cancel()`](crate::Client::retry_until_included_with_cancel).
+///
+/// Clones share the cancellation state, so it can be triggered from another task.
+#[derive(Clone, Debug, Default)]
+pub struct RetryCancel(Arc<AtomicBool>);
+
+impl RetryCancel {
+    /// Creates a new [`RetryCancel`] that isn't triggered yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the retrying, it returns the blocks reattached so far at its next check.
+    pub fn trigger(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the retrying got cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
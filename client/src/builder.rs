@@ -4,11 +4,12 @@
 //! Builder of the Client Instance
 use std::{
     collections::HashMap,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
     time::Duration,
 };
 
 use iota_types::block::{
+    input::INPUT_COUNT_MAX,
     output::dto::RentStructureDto,
     protocol::{dto::ProtocolParametersDto, ProtocolParameters},
 };
@@ -19,14 +20,29 @@ use tokio::runtime::Runtime;
 use crate::node_api::mqtt::{BrokerOptions, MqttEvent};
 use crate::{
     client::Client,
-    constants::{DEFAULT_API_TIMEOUT, DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_TIPS_INTERVAL},
+    constants::{
+        DEFAULT_API_TIMEOUT, DEFAULT_NODE_CLOCK_SKEW_TOLERANCE, DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_TIPS_INTERVAL,
+    },
     error::Result,
+    node_api::core::output_cache::{OutputCache, OutputCacheOptions},
     node_manager::{
-        builder::validate_url,
+        builder::{validate_url, RetryPolicy},
         node::{Node, NodeAuth},
     },
 };
 
+/// Encoding used to post blocks to the node
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BlockEncoding {
+    /// Packed bytes, see [`Client::post_block_raw()`]
+    #[default]
+    Raw,
+    /// JSON, see [`Client::post_block()`]
+    Json,
+    /// Packed bytes if the packed block is larger than the given number of bytes, JSON otherwise
+    Auto(usize),
+}
+
 /// Struct containing network and PoW related information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct NetworkInfo {
@@ -131,12 +147,39 @@ pub struct ClientBuilder {
     /// The amount of threads to be used for proof of work
     #[serde(rename = "powWorkerCount", default)]
     pub pow_worker_count: Option<usize>,
+    /// Encoding used to post blocks built by the client
+    #[serde(rename = "blockEncoding", default)]
+    pub block_encoding: BlockEncoding,
+    /// Tolerated difference between the local time and the time of the node when evaluating timelocks and
+    /// expirations of inputs
+    #[serde(rename = "timeSkewTolerance", default)]
+    pub time_skew_tolerance: Duration,
+    /// Tolerated difference between the latest milestone timestamps of the nodes when checking the local time
+    #[serde(rename = "nodeClockSkewTolerance", default = "default_node_clock_skew_tolerance")]
+    pub node_clock_skew_tolerance: Duration,
+    /// Maximum amount of inputs a transaction built by the client can have before consolidation is required
+    #[serde(
+        rename = "inputConsolidationThreshold",
+        default = "default_input_consolidation_threshold"
+    )]
+    pub input_consolidation_threshold: usize,
+    /// Options for caching the outputs requested by the client, no outputs are cached if not set
+    #[serde(rename = "outputCache", default)]
+    pub output_cache: Option<OutputCacheOptions>,
 }
 
 fn default_api_timeout() -> Duration {
     DEFAULT_API_TIMEOUT
 }
 
+fn default_input_consolidation_threshold() -> usize {
+    INPUT_COUNT_MAX.into()
+}
+
+fn default_node_clock_skew_tolerance() -> Duration {
+    DEFAULT_NODE_CLOCK_SKEW_TOLERANCE
+}
+
 fn default_remote_pow_timeout() -> Duration {
     DEFAULT_REMOTE_POW_API_TIMEOUT
 }
@@ -164,6 +207,11 @@ impl Default for ClientBuilder {
             api_timeout: DEFAULT_API_TIMEOUT,
             remote_pow_timeout: DEFAULT_REMOTE_POW_API_TIMEOUT,
             pow_worker_count: None,
+            block_encoding: BlockEncoding::default(),
+            time_skew_tolerance: Duration::ZERO,
+            node_clock_skew_tolerance: DEFAULT_NODE_CLOCK_SKEW_TOLERANCE,
+            input_consolidation_threshold: INPUT_COUNT_MAX.into(),
+            output_cache: None,
         }
     }
 }
@@ -248,6 +296,21 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the maximum amount of nodes that are requested at once while syncing the nodes.
+    /// Default is 10.
+    pub fn with_sync_concurrency(mut self, sync_concurrency: usize) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_sync_concurrency(sync_concurrency);
+        self
+    }
+
+    /// Sets the maximum amount of milestones the latest milestone of a node can lag behind the most recent one of the
+    /// other nodes of the network. Nodes lagging further behind aren't used until they caught up.
+    /// Default is 15.
+    pub fn with_max_milestone_lag(mut self, max_milestone_lag: u32) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_max_milestone_lag(max_milestone_lag);
+        self
+    }
+
     /// Ignores the node health status.
     /// Every node will be considered healthy and ready to use.
     pub fn with_ignore_node_health(mut self) -> Self {
@@ -255,6 +318,13 @@ impl ClientBuilder {
         self
     }
 
+    /// Disables the syncing of the nodes, no background task is spawned and no node info is requested.
+    /// Every node will be considered synced and ready to use, the [NetworkInfo] then needs to be set manually.
+    pub fn with_node_sync_disabled(mut self) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_node_sync_disabled();
+        self
+    }
+
     /// Set if quorum should be used or not
     pub fn with_quorum(mut self, quorum: bool) -> Self {
         self.node_manager_builder = self.node_manager_builder.with_quorum(quorum);
@@ -325,13 +395,78 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the encoding used to post blocks built by the client, for example in
+    /// [`ClientBlockBuilder::finish()`](crate::api::ClientBlockBuilder::finish()).
+    /// Default is [`BlockEncoding::Raw`]; [`BlockEncoding::Auto`] picks the encoding depending on the size of each
+    /// block.
+    pub fn with_block_encoding(mut self, block_encoding: BlockEncoding) -> Self {
+        self.block_encoding = block_encoding;
+        self
+    }
+
+    /// Sets the tolerated difference between the local time and the time of the node. Inputs with a timelock or an
+    /// expiration within this tolerance of the local time aren't used, since the node could evaluate them differently.
+    /// Default is no tolerance.
+    pub fn with_time_skew_tolerance(mut self, time_skew_tolerance: Duration) -> Self {
+        self.time_skew_tolerance = time_skew_tolerance;
+        self
+    }
+
+    /// Sets the tolerated difference between the latest milestone timestamps of the nodes that are asked for the
+    /// time, before the local time is checked against their median. Default is 60 seconds.
+    pub fn with_node_clock_skew_tolerance(mut self, node_clock_skew_tolerance: Duration) -> Self {
+        self.node_clock_skew_tolerance = node_clock_skew_tolerance;
+        self
+    }
+
+    /// Sets the maximum amount of inputs a transaction built by the client can have. If more inputs would be needed,
+    /// [`Error::ConsolidationRequired`](crate::Error::ConsolidationRequired) is returned instead. Values above the
+    /// protocol maximum of 128 inputs have no effect. Default is 128.
+    pub fn with_input_consolidation_threshold(mut self, input_consolidation_threshold: usize) -> Self {
+        self.input_consolidation_threshold = input_consolidation_threshold;
+        self
+    }
+
+    /// Enables an in-memory cache for the outputs requested by the client, which keeps up to `capacity` outputs and
+    /// removes the least recently used one when it's full. Spent outputs stay cached, since they can't change anymore,
+    /// unspent outputs are requested again after `ttl`. Default is no cache.
+    pub fn with_output_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.output_cache = Some(OutputCacheOptions { capacity, ttl });
+        self
+    }
+
+    /// Sets the policy for retrying requests that failed because of a transient error.
+    /// Default is no retries.
+    pub fn with_http_retry(mut self, policy: RetryPolicy) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_http_retry(policy);
+        self
+    }
+
+    /// Sets the maximum amount of idle connections that are kept open per node to be reused by later requests.
+    /// Default is no limit.
+    pub fn with_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_pool_max_idle_per_host(pool_max_idle_per_host);
+        self
+    }
+
+    /// Sets the time after which idle connections to a node get closed. Default is 90 seconds.
+    pub fn with_pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_pool_idle_timeout(pool_idle_timeout);
+        self
+    }
+
     /// Build the Client instance.
     pub fn finish(self) -> Result<Client> {
         let network_info = Arc::new(RwLock::new(self.network_info));
         let healthy_nodes = Arc::new(RwLock::new(HashMap::new()));
+        let http_client = self.node_manager_builder.http_client()?;
 
         #[cfg(not(target_family = "wasm"))]
-        let (runtime, sync_handle) = {
+        let (runtime, sync_handle) = if self.node_manager_builder.node_sync_disabled {
+            (None, None)
+        } else {
             let nodes = self
                 .node_manager_builder
                 .primary_node
@@ -342,24 +477,31 @@ impl ClientBuilder {
 
             let healthy_nodes_ = healthy_nodes.clone();
             let network_info_ = network_info.clone();
+            let http_client_ = http_client.clone();
 
             let (runtime, sync_handle) = std::thread::spawn(move || {
                 let runtime = Runtime::new().expect("failed to create Tokio runtime");
                 if let Err(e) = runtime.block_on(Client::sync_nodes(
+                    &http_client_,
                     &healthy_nodes_,
                     &nodes,
                     &network_info_,
                     self.node_manager_builder.ignore_node_health,
+                    self.node_manager_builder.sync_concurrency,
+                    self.node_manager_builder.max_milestone_lag,
                 )) {
                     panic!("failed to sync nodes: {e:?}");
                 }
                 let sync_handle = Client::start_sync_process(
                     &runtime,
+                    http_client_,
                     healthy_nodes_,
                     nodes,
                     self.node_manager_builder.node_sync_interval,
                     network_info_,
                     self.node_manager_builder.ignore_node_health,
+                    self.node_manager_builder.sync_concurrency,
+                    self.node_manager_builder.max_milestone_lag,
                 );
                 (runtime, sync_handle)
             })
@@ -371,7 +513,7 @@ impl ClientBuilder {
         #[cfg(feature = "mqtt")]
         let (mqtt_event_tx, mqtt_event_rx) = tokio::sync::watch::channel(MqttEvent::Connected);
         let client = Client {
-            node_manager: self.node_manager_builder.build(healthy_nodes),
+            node_manager: self.node_manager_builder.build(healthy_nodes, http_client),
             #[cfg(not(target_family = "wasm"))]
             runtime,
             #[cfg(not(target_family = "wasm"))]
@@ -388,6 +530,12 @@ impl ClientBuilder {
             api_timeout: self.api_timeout,
             remote_pow_timeout: self.remote_pow_timeout,
             pow_worker_count: self.pow_worker_count,
+            block_encoding: self.block_encoding,
+            time_skew_tolerance: self.time_skew_tolerance,
+            node_clock_skew_tolerance: self.node_clock_skew_tolerance,
+            input_consolidation_threshold: self.input_consolidation_threshold,
+            stale_cache: Default::default(),
+            output_cache: Arc::new(Mutex::new(OutputCache::new(self.output_cache))),
         };
         Ok(client)
     }
@@ -4,13 +4,18 @@
 //! The Client module to connect through HORNET or Bee with API usages
 
 use std::{
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
     time::Duration,
 };
 
-use iota_types::block::{output::RentStructure, protocol::ProtocolParameters};
+use iota_types::block::{
+    input::INPUT_COUNT_MAX,
+    output::{Output, Rent, RentStructure},
+    protocol::ProtocolParameters,
+};
 #[cfg(not(target_family = "wasm"))]
 use tokio::runtime::Runtime;
+use url::Url;
 #[cfg(feature = "mqtt")]
 use {
     crate::node_api::mqtt::{BrokerOptions, MqttEvent, TopicHandlerMap},
@@ -19,9 +24,14 @@ use {
 };
 
 use crate::{
-    builder::{ClientBuilder, NetworkInfo},
+    builder::{BlockEncoding, ClientBuilder, NetworkInfo},
     constants::DEFAULT_TIPS_INTERVAL,
     error::Result,
+    node_api::core::{
+        output_cache::{OutputCache, OutputCacheOptions},
+        stale::StaleCache,
+    },
+    node_manager::node::Node,
 };
 
 /// An instance of the client using HORNET or Bee URI
@@ -52,6 +62,91 @@ pub struct Client {
     #[allow(dead_code)] // not used for wasm
     /// pow_worker_count for local PoW.
     pub(crate) pow_worker_count: Option<usize>,
+    /// Encoding used to post blocks built by the client.
+    pub(crate) block_encoding: BlockEncoding,
+    /// Tolerated difference between the local time and the time of the node.
+    pub(crate) time_skew_tolerance: Duration,
+    /// Tolerated difference between the latest milestone timestamps of the nodes.
+    pub(crate) node_clock_skew_tolerance: Duration,
+    /// Maximum amount of inputs of a transaction before consolidation is required.
+    pub(crate) input_consolidation_threshold: usize,
+    /// Last successful results of the read methods with a stale fallback.
+    pub(crate) stale_cache: Arc<RwLock<StaleCache>>,
+    /// Outputs cached by `get_output`.
+    pub(crate) output_cache: Arc<Mutex<OutputCache>>,
+}
+
+/// The effective configuration of a [`Client`], for diagnostics.
+///
+/// It doesn't contain any secrets: node authentication isn't included and passwords are removed from the node urls.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ClientDebugInfo {
+    /// The primary node.
+    #[serde(rename = "primaryNode")]
+    pub primary_node: Option<Url>,
+    /// The primary node for remote proof of work.
+    #[serde(rename = "primaryPowNode")]
+    pub primary_pow_node: Option<Url>,
+    /// The configured nodes.
+    pub nodes: Vec<Url>,
+    /// The configured permanodes.
+    pub permanodes: Option<Vec<Url>>,
+    /// The nodes that were healthy at the last sync.
+    #[serde(rename = "healthyNodes")]
+    pub healthy_nodes: Vec<Url>,
+    /// Whether the node health is ignored.
+    #[serde(rename = "ignoreNodeHealth")]
+    pub ignore_node_health: bool,
+    /// Whether the node syncing is disabled.
+    #[serde(rename = "nodeSyncDisabled")]
+    pub node_sync_disabled: bool,
+    /// The interval of the node syncing.
+    #[serde(rename = "nodeSyncInterval")]
+    pub node_sync_interval: Duration,
+    /// Whether quorum is used for requests.
+    pub quorum: bool,
+    /// The minimum number of nodes for a quorum.
+    #[serde(rename = "minQuorumSize")]
+    pub min_quorum_size: usize,
+    /// The percentage of nodes that have to agree for a quorum.
+    #[serde(rename = "quorumThreshold")]
+    pub quorum_threshold: usize,
+    /// The network and proof of work related information.
+    #[serde(rename = "networkInfo")]
+    pub network_info: NetworkInfo,
+    /// The HTTP request timeout.
+    #[serde(rename = "apiTimeout")]
+    pub api_timeout: Duration,
+    /// The HTTP request timeout for remote proof of work.
+    #[serde(rename = "remotePowTimeout")]
+    pub remote_pow_timeout: Duration,
+    /// The amount of threads used for local proof of work.
+    #[serde(rename = "powWorkerCount")]
+    pub pow_worker_count: Option<usize>,
+    /// The encoding used to post blocks.
+    #[serde(rename = "blockEncoding")]
+    pub block_encoding: BlockEncoding,
+    /// The tolerated difference between the local time and the time of the node.
+    #[serde(rename = "timeSkewTolerance")]
+    pub time_skew_tolerance: Duration,
+    /// The tolerated difference between the latest milestone timestamps of the nodes.
+    #[serde(rename = "nodeClockSkewTolerance")]
+    pub node_clock_skew_tolerance: Duration,
+    /// The maximum amount of inputs of a transaction before consolidation is required.
+    #[serde(rename = "inputConsolidationThreshold")]
+    pub input_consolidation_threshold: usize,
+    /// The options of the output cache.
+    #[serde(rename = "outputCache")]
+    pub output_cache: Option<OutputCacheOptions>,
+}
+
+// Removes the credentials from a node url.
+pub(crate) fn redacted_url(node: &Node) -> Url {
+    let mut url = node.url.clone();
+    // Only fails for urls that can't have credentials in the first place.
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url
 }
 
 impl std::fmt::Debug for Client {
@@ -89,7 +184,8 @@ impl Drop for Client {
         std::thread::spawn(move || {
             crate::async_runtime::block_on(async move {
                 if let Some(mqtt_client) = mqtt_client.write().await.take() {
-                    mqtt_client.disconnect().await.unwrap();
+                    // Fails if the event loop already stopped, because the connection was lost permanently.
+                    let _ = mqtt_client.disconnect().await;
                 }
             });
         })
@@ -120,6 +216,17 @@ impl Client {
         Ok(self.network_info.read().map_err(|_| crate::Error::PoisonError)?.clone())
     }
 
+    /// Requests the node info and updates the cached NetworkInfo with it, without waiting for the next node sync.
+    /// Useful after a network upgrade changed parameters like the bech32 HRP or the min PoW score.
+    pub async fn refresh_network_info(&self) -> Result<NetworkInfo> {
+        let info = self.get_info().await?.node_info;
+        let mut client_network_info = self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+        client_network_info.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
+        client_network_info.protocol_parameters = info.protocol.try_into()?;
+
+        Ok(client_network_info.clone())
+    }
+
     /// Gets the protocol parameters of the node we're connecting to.
     pub async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
         Ok(self.get_network_info().await?.protocol_parameters)
@@ -165,6 +272,14 @@ impl Client {
             .clone())
     }
 
+    /// Gets the sum of the minimum storage deposits of the outputs, with the rent structure of the node we're
+    /// connecting to.
+    pub async fn minimum_storage_deposit(&self, outputs: &[Output]) -> Result<u64> {
+        let rent_structure = self.get_rent_structure().await?;
+
+        Ok(outputs.iter().map(|output| output.rent_cost(&rent_structure)).sum())
+    }
+
     /// Gets the token supply of the node we're connecting to.
     pub async fn get_token_supply(&self) -> Result<u64> {
         Ok(self.get_network_info().await?.protocol_parameters.token_supply())
@@ -184,6 +299,74 @@ impl Client {
             .map_or(NetworkInfo::default().local_pow, |info| info.local_pow)
     }
 
+    /// Gets the effective configuration of the client for diagnostics, without secrets.
+    pub fn debug_config(&self) -> Result<ClientDebugInfo> {
+        let node_manager = &self.node_manager;
+        let mut nodes = node_manager.nodes.iter().map(redacted_url).collect::<Vec<_>>();
+        nodes.sort();
+        let permanodes = node_manager.permanodes.as_ref().map(|permanodes| {
+            let mut permanodes = permanodes.iter().map(redacted_url).collect::<Vec<_>>();
+            permanodes.sort();
+            permanodes
+        });
+        let mut healthy_nodes = node_manager
+            .healthy_nodes
+            .read()
+            .map_err(|_| crate::Error::PoisonError)?
+            .keys()
+            .map(redacted_url)
+            .collect::<Vec<_>>();
+        healthy_nodes.sort();
+
+        Ok(ClientDebugInfo {
+            primary_node: node_manager.primary_node.as_ref().map(redacted_url),
+            primary_pow_node: node_manager.primary_pow_node.as_ref().map(redacted_url),
+            nodes,
+            permanodes,
+            healthy_nodes,
+            ignore_node_health: node_manager.ignore_node_health,
+            node_sync_disabled: node_manager.node_sync_disabled,
+            node_sync_interval: node_manager.node_sync_interval,
+            quorum: node_manager.quorum,
+            min_quorum_size: node_manager.min_quorum_size,
+            quorum_threshold: node_manager.quorum_threshold,
+            network_info: self.network_info.read().map_err(|_| crate::Error::PoisonError)?.clone(),
+            api_timeout: self.api_timeout,
+            remote_pow_timeout: self.remote_pow_timeout,
+            pow_worker_count: self.pow_worker_count,
+            block_encoding: self.block_encoding,
+            time_skew_tolerance: self.time_skew_tolerance,
+            node_clock_skew_tolerance: self.node_clock_skew_tolerance,
+            input_consolidation_threshold: self.input_consolidation_threshold,
+            output_cache: self
+                .output_cache
+                .lock()
+                .map_err(|_| crate::Error::PoisonError)?
+                .options(),
+        })
+    }
+
+    /// Gets the encoding used to post blocks built by the client.
+    pub fn get_block_encoding(&self) -> BlockEncoding {
+        self.block_encoding
+    }
+
+    /// Gets the tolerated difference between the local time and the time of the node.
+    pub fn get_time_skew_tolerance(&self) -> Duration {
+        self.time_skew_tolerance
+    }
+
+    /// Gets the tolerated difference between the latest milestone timestamps of the nodes.
+    pub fn get_node_clock_skew_tolerance(&self) -> Duration {
+        self.node_clock_skew_tolerance
+    }
+
+    /// Gets the maximum amount of inputs of a transaction before consolidation is required, capped at the protocol
+    /// maximum.
+    pub fn get_consolidation_threshold(&self) -> usize {
+        self.input_consolidation_threshold.min(INPUT_COUNT_MAX.into())
+    }
+
     pub(crate) fn get_timeout(&self) -> Duration {
         self.api_timeout
     }
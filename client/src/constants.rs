@@ -10,18 +10,29 @@ pub(crate) const DEFAULT_API_TIMEOUT: Duration = Duration::from_secs(15);
 pub(crate) const DEFAULT_REMOTE_POW_API_TIMEOUT: Duration = Duration::from_secs(100);
 pub(crate) const DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL: u64 = 1;
 pub(crate) const DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT: u64 = 40;
+/// Interval in which a waiting retry until included checks if it got cancelled
+pub(crate) const RETRY_UNTIL_INCLUDED_CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 /// Interval in seconds when new tips will be requested during PoW, so the final block always will be attached to a
 /// new part of the Tangle
 pub(crate) const DEFAULT_TIPS_INTERVAL: u64 = 5;
 /// Interval in which the node info will be requested and healthy nodes will be added to the healthy node pool
 pub(crate) const NODE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+/// Amount of the latest request latencies per node that are used for the moving average
+pub(crate) const NODE_LATENCY_SAMPLE_WINDOW: usize = 10;
 pub(crate) const DEFAULT_MIN_QUORUM_SIZE: usize = 3;
 pub(crate) const DEFAULT_QUORUM_THRESHOLD: usize = 66;
 pub(crate) const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
-#[cfg(not(target_family = "wasm"))]
+/// Default maximum of nodes that are requested at once while syncing the nodes
+pub(crate) const DEFAULT_SYNC_CONCURRENCY: usize = 10;
+/// Default maximum amount of milestones a node can lag behind the most recent node to still be used
+pub(crate) const DEFAULT_MAX_MILESTONE_LAG: u32 = 15;
 pub(crate) const MAX_PARALLEL_API_REQUESTS: usize = 100;
+/// Maximum amount of outputs and milestones that are kept for the stale fallback of the read methods
+pub(crate) const STALE_CACHE_MAX_ENTRIES: usize = 1000;
 /// Max allowed difference between the local time and latest milestone time, 5 minutes in seconds
 pub(crate) const FIVE_MINUTES_IN_SECONDS: u32 = 300;
+/// Default max allowed difference between the latest milestone timestamps of the nodes that are asked for the time
+pub(crate) const DEFAULT_NODE_CLOCK_SKEW_TOLERANCE: Duration = Duration::from_secs(60);
 
 /// Bech32 hrp for the IOTA mainnet <https://github.com/satoshilabs/slips/blob/master/slip-0173.md>
 pub const IOTA_BECH32_HRP: &str = "iota";
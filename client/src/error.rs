@@ -5,14 +5,17 @@
 
 use std::fmt::Debug;
 
-use iota_types::block::semantic::ConflictReason;
+use iota_types::block::{output::OutputId, semantic::ConflictReason};
 use packable::error::UnexpectedEOF;
 use serde::{
     ser::{SerializeMap, Serializer},
     Serialize,
 };
 
-use crate::{api::input_selection::Error as InputSelectionError, node_api::indexer::QueryParameter};
+use crate::{
+    api::{input_selection::Error as InputSelectionError, ScanCursor},
+    node_api::indexer::QueryParameter,
+};
 
 /// Type alias of `Result` in iota-client
 pub type Result<T> = std::result::Result<T, Error>;
@@ -21,9 +24,27 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::large_enum_variant)]
 pub enum Error {
+    /// An address scan was interrupted by an error, it can be resumed with the cursor
+    #[error("address scan interrupted at address index {}: {error}", cursor.next_index)]
+    AddressScanInterrupted {
+        /// The position to resume the scan from.
+        cursor: ScanCursor,
+        /// The output ids found before the interruption.
+        output_ids: Vec<OutputId>,
+        /// The error that interrupted the scan.
+        error: Box<Self>,
+    },
     /// Block dtos error
     #[error("{0}")]
     ApiTypes(#[from] iota_types::api::core::error::Error),
+    /// The HRP of a Bech32 address doesn't match the one of the network
+    #[error("bech32 hrp mismatch: expected {expected}, found {found}")]
+    Bech32HrpMismatch {
+        /// The HRP of the network.
+        expected: String,
+        /// The HRP of the address.
+        found: String,
+    },
     /// Blake2b256 Error
     #[error("{0}")]
     Blake2b256(&'static str),
@@ -33,12 +54,28 @@ pub enum Error {
     /// Block types error
     #[error("{0}")]
     Block(#[from] iota_types::block::Error),
+    /// The block is larger than the max block length nodes accept
+    #[error("the block is too large. Its length is {size}, max length is {max}")]
+    BlockTooLarge {
+        /// The length of the packed block.
+        size: usize,
+        /// The max supported length.
+        max: usize,
+    },
     /// The wallet account has enough funds, but split on too many outputs
-    #[error("the wallet account has enough funds, but split on too many outputs: {0}, max. is 128, consolidate them")]
-    ConsolidationRequired(usize),
+    #[error("enough funds, but split on too many outputs: {needed}, max. is {limit}, consolidate them")]
+    ConsolidationRequired {
+        /// The maximum amount of inputs.
+        limit: usize,
+        /// The amount of inputs that would be needed.
+        needed: usize,
+    },
     /// Crypto.rs error
     #[error("{0}")]
     Crypto(#[from] crypto::Error),
+    /// The nodes don't provide an API that is needed
+    #[error("no node supports {0}")]
+    FeatureNotSupportedByNode(String),
     /// Address not found
     #[error("address: {address} not found in range: {range}")]
     InputAddressNotFound {
@@ -47,15 +84,48 @@ pub enum Error {
         /// The range in which the address was not found.
         range: String,
     },
+    /// An input couldn't be resolved to its output, most likely because it got pruned
+    #[error("input {0} couldn't be resolved to an output, it might have been pruned")]
+    InputPruned(String),
+    /// The balance is lower than required for a conditional transfer
+    #[error("insufficient balance for the condition: found {found}, required {required}")]
+    InsufficientBalanceForCondition {
+        /// The balance of the account.
+        found: u64,
+        /// The required minimum balance.
+        required: u64,
+    },
+    /// The node offers fewer tips than the requested amount of parents
+    #[error("insufficient tips: requested {requested}, the node offers {available}")]
+    InsufficientTips {
+        /// The requested amount of parents.
+        requested: u8,
+        /// The amount of tips the node offers.
+        available: usize,
+    },
     /// Invalid amount in API response
     #[error("invalid amount in API response: {0}")]
     InvalidAmount(String),
+    /// Amount filters of an indexer query that no output can match
+    #[error("invalid amount range: greater than {greater_than} and less than {less_than}")]
+    InvalidAmountRange {
+        /// The lower bound.
+        greater_than: u64,
+        /// The upper bound.
+        less_than: u64,
+    },
     /// Invalid BIP32 chain data
     #[error("invalid BIP32 chain data")]
     InvalidBIP32ChainData,
+    /// A block of chunked data doesn't contain a data chunk
+    #[error("block {0} doesn't contain a data chunk")]
+    InvalidDataChunk(String),
     /// Invalid mnemonic error
     #[error("invalid mnemonic {0}")]
     InvalidMnemonic(String),
+    /// Unsupported number of mnemonic words
+    #[error("invalid mnemonic length {0}, expected 12, 15, 18, 21 or 24 words")]
+    InvalidMnemonicLength(usize),
     /// The transaction essence is too large
     #[error("the transaction essence is too large. Its length is {length}, max length is {max_length}")]
     InvalidRegularTransactionEssenceLength {
@@ -75,12 +145,26 @@ pub enum Error {
     /// JSON error
     #[error("{0}")]
     Json(#[from] serde_json::Error),
+    /// The data of a milestone isn't available anymore
+    #[error("milestone {0} was pruned by the node")]
+    MilestonePruned(u32),
     /// Missing required parameters
     #[error("must provide required parameter: {0}")]
     MissingParameter(&'static str),
+    /// The block doesn't contain a transaction payload
+    #[error("block ID `{0}` doesn't contain a transaction payload")]
+    MissingTransactionPayload(String),
     /// Error on API request
     #[error("node error: {0}")]
     Node(String),
+    /// The latest milestone timestamps of the nodes differ too much to check the local time
+    #[error("the latest milestone timestamps of the nodes differ by {spread}s, more than the tolerated {tolerance}s")]
+    NodeClockSkew {
+        /// The difference between the earliest and the latest timestamp in seconds.
+        spread: u32,
+        /// The tolerated difference in seconds.
+        tolerance: u32,
+    },
     /// The block doesn't need to be promoted or reattached
     #[error("block ID `{0}` doesn't need to be promoted or reattached")]
     NoNeedPromoteOrReattach(String),
@@ -90,6 +174,12 @@ pub enum Error {
     /// Output Error
     #[error("output error: {0}")]
     Output(&'static str),
+    /// The indexer has more pages of output ids than allowed
+    #[error("the indexer has more than {max_pages} pages of output ids")]
+    OutputIdsPageLimit {
+        /// The maximum amount of pages.
+        max_pages: usize,
+    },
     /// PlaceholderSecretManager can't be used for address generation or signing
     #[error("placeholderSecretManager can't be used for address generation or signing")]
     PlaceholderSecretManager,
@@ -118,6 +208,9 @@ pub enum Error {
         /// The minimum quorum threshold.
         minimum_threshold: usize,
     },
+    /// The reference block isn't a tip of the node
+    #[error("reference block ID `{0}` isn't a tip")]
+    ReferenceNotTip(String),
     /// Error from RestAPI calls with unexpected status code response
     #[error("response error with status code {code}: {text}, URL: {url}")]
     ResponseError {
@@ -137,6 +230,14 @@ pub enum Error {
     /// No node available in the healthy node pool
     #[error("no healthy node available")]
     HealthyNodePoolEmpty,
+    /// The tag of a tagged data payload is too long
+    #[error("the tag is too long. Its length is {length}, max length is {max_length}")]
+    TagTooLong {
+        /// The found length.
+        length: usize,
+        /// The max supported length.
+        max_length: usize,
+    },
     /// Error when building tagged_data blocks
     #[error("error when building tagged_data block: {0}")]
     TaggedData(String),
@@ -261,6 +362,21 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
     #[error("Stronghold reported a procedure error: {0}")]
     StrongholdProcedure(#[from] iota_stronghold::procedures::ProcedureError),
+    /// Backing up the previous Stronghold snapshot file failed
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    #[error("failed to back up the stronghold snapshot: {0}")]
+    StrongholdSnapshotBackup(std::io::Error),
+    /// The Stronghold snapshot file is truncated, malformed or not a snapshot at all
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    #[error("the stronghold snapshot is corrupted")]
+    StrongholdSnapshotCorrupted,
+    /// The Stronghold snapshot file has been written with an unsupported snapshot version
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    #[error("unsupported stronghold snapshot version")]
+    StrongholdVersionMismatch,
 }
 
 // map most errors to a single error but there are some errors that
@@ -72,7 +72,7 @@ pub use url::Url;
 #[cfg(feature = "mqtt")]
 pub use self::node_api::mqtt;
 pub use self::{
-    builder::{ClientBuilder, NetworkInfo, NetworkInfoDto},
+    builder::{BlockEncoding, ClientBuilder, NetworkInfo, NetworkInfoDto},
     client::*,
     error::*,
     node_api::core::routes::NodeInfoWrapper,
@@ -1,6 +1,7 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crypto::keys::slip10::Chain;
 use iota_types::block::{
     address::AliasAddress,
     output::{
@@ -192,6 +193,16 @@ pub enum Message {
         #[serde(rename = "remainderData")]
         remainder_data: Option<RemainderDataDto>,
     },
+    /// Sign a message with the Ed25519 key derived at the given chain.
+    SignEd25519 {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// The hex encoded message to sign
+        message: String,
+        /// The chain to derive the signing key
+        chain: Chain,
+    },
     /// Store a mnemonic in the Stronghold vault
     #[cfg(feature = "stronghold")]
     #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
@@ -521,6 +532,12 @@ pub enum Message {
     },
     /// Generates a new mnemonic.
     GenerateMnemonic,
+    /// Generates a new mnemonic with the given number of words.
+    GenerateMnemonicWithWordCount {
+        /// Number of words, has to be 12, 15, 18, 21 or 24
+        #[serde(rename = "wordCount")]
+        word_count: usize,
+    },
     /// Returns a hex encoded seed for a mnemonic.
     MnemonicToHexSeed {
         /// Mnemonic
@@ -572,4 +589,14 @@ pub enum Message {
         /// The transaction essence
         essence: TransactionEssenceDto,
     },
+    /// Verify an Ed25519 signature of a message.
+    VerifyEd25519Signature {
+        /// The hex encoded public key
+        #[serde(rename = "publicKey")]
+        public_key: String,
+        /// The hex encoded signature
+        signature: String,
+        /// The hex encoded message
+        message: String,
+    },
 }
@@ -4,6 +4,7 @@
 use std::{any::Any, panic::AssertUnwindSafe};
 
 use backtrace::Backtrace;
+use crypto::signatures::ed25519::{PublicKey, Signature};
 use futures::{Future, FutureExt};
 use iota_types::block::{
     address::dto::AddressDto,
@@ -114,6 +115,12 @@ impl ClientMessageHandler {
                     MqttPayload::Json(val) => {
                         serde_json::to_string(&val).expect("failed to serialize MqttPayload::Json")
                     }
+                    MqttPayload::BlockMetadata(metadata) => {
+                        serde_json::to_string(&metadata).expect("failed to serialize MqttPayload::BlockMetadata")
+                    }
+                    MqttPayload::Output(output) => {
+                        serde_json::to_string(&output).expect("failed to serialize MqttPayload::Output")
+                    }
                     MqttPayload::Block(block) => {
                         serde_json::to_string(&BlockDto::from(block)).expect("failed to serialize MqttPayload::Block")
                     }
@@ -121,6 +128,8 @@ impl ClientMessageHandler {
                         .expect("failed to serialize MqttPayload::MilestonePayload"),
                     MqttPayload::Receipt(receipt) => serde_json::to_string(&ReceiptMilestoneOptionDto::from(receipt))
                         .expect("failed to serialize MqttPayload::Receipt"),
+                    MqttPayload::Disconnected => serde_json::to_string(&topic_event.payload)
+                        .expect("failed to serialize MqttPayload::Disconnected"),
                 };
                 let response = MqttResponse {
                     topic: topic_event.topic.clone(),
@@ -163,6 +172,15 @@ impl ClientMessageHandler {
                     "Response: SignTransaction{{ secret_manager: <omitted>, prepared_transaction_data: {prepared_transaction_data:?} }}"
                 )
             }
+            Message::SignEd25519 {
+                secret_manager: _,
+                message,
+                chain,
+            } => {
+                log::debug!(
+                    "Response: SignEd25519{{ secret_manager: <omitted>, message: {message}, chain: {chain:?} }}"
+                )
+            }
             #[cfg(feature = "stronghold")]
             Message::StoreMnemonic { .. } => {
                 log::debug!("Response: StoreMnemonic{{ <omitted> }}")
@@ -445,6 +463,20 @@ impl ClientMessageHandler {
 
                 Ok(Response::SignatureUnlock((&unlock).into()))
             }
+            Message::SignEd25519 {
+                secret_manager,
+                message,
+                chain,
+            } => {
+                let message: Vec<u8> = prefix_hex::decode(message)?;
+                if message.is_empty() {
+                    return Err(DtoError::InvalidField("message can't be empty").into());
+                }
+                let secret_manager: SecretManager = (&secret_manager).try_into()?;
+                let signature = secret_manager.sign_ed25519(&message, &chain).await?;
+
+                Ok(Response::Ed25519Signature((&signature).into()))
+            }
             #[cfg(feature = "stronghold")]
             Message::StoreMnemonic {
                 secret_manager,
@@ -576,7 +608,7 @@ impl ClientMessageHandler {
                 interval,
                 max_attempts,
             } => {
-                let res = self
+                let (res, _) = self
                     .client
                     .retry_until_included(&block_id, interval, max_attempts)
                     .await?;
@@ -644,6 +676,9 @@ impl ClientMessageHandler {
             ))),
             Message::IsAddressValid { address } => Ok(Response::IsAddressValid(Client::is_address_valid(&address))),
             Message::GenerateMnemonic => Ok(Response::GeneratedMnemonic(Client::generate_mnemonic()?)),
+            Message::GenerateMnemonicWithWordCount { word_count } => Ok(Response::GeneratedMnemonic(
+                Client::generate_mnemonic_with_word_count(word_count)?,
+            )),
             Message::MnemonicToHexSeed { mut mnemonic } => {
                 let response = Response::MnemonicHexSeed(Client::mnemonic_to_hex_seed(&mnemonic)?);
 
@@ -674,6 +709,19 @@ impl ClientMessageHandler {
             Message::HashTransactionEssence { essence } => Ok(Response::TransactionEssenceHash(prefix_hex::encode(
                 TransactionEssence::try_from_dto_unverified(&essence)?.hash(),
             ))),
+            Message::VerifyEd25519Signature {
+                public_key,
+                signature,
+                message,
+            } => {
+                let public_key = PublicKey::try_from_bytes(prefix_hex::decode(public_key)?)?;
+                let signature = Signature::from_bytes(prefix_hex::decode(signature)?);
+                let message: Vec<u8> = prefix_hex::decode(message)?;
+
+                Ok(Response::VerifiedEd25519Signature(
+                    public_key.verify(&signature, &message),
+                ))
+            }
         }
     }
 }
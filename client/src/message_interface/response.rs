@@ -27,6 +27,7 @@ use iota_types::{
             transaction::TransactionId,
         },
         protocol::dto::ProtocolParametersDto,
+        signature::dto::Ed25519SignatureDto,
         unlock::dto::UnlockDto,
         BlockDto, BlockId,
     },
@@ -92,6 +93,9 @@ pub enum Response {
     /// - [`SignatureUnlock`](crate::message_interface::Message::SignatureUnlock)
     SignatureUnlock(UnlockDto),
     /// Response for:
+    /// - [`SignEd25519`](crate::message_interface::Message::SignEd25519)
+    Ed25519Signature(Ed25519SignatureDto),
+    /// Response for:
     /// - [`UnhealthyNodes`](crate::message_interface::Message::UnhealthyNodes)
     #[cfg(not(target_family = "wasm"))]
     UnhealthyNodes(HashSet<Node>),
@@ -203,6 +207,7 @@ pub enum Response {
     IsAddressValid(bool),
     /// Response for:
     /// - [`GenerateMnemonic`](crate::message_interface::Message::GenerateMnemonic)
+    /// - [`GenerateMnemonicWithWordCount`](crate::message_interface::Message::GenerateMnemonicWithWordCount)
     GeneratedMnemonic(String),
     /// Response for:
     /// - [`MnemonicToHexSeed`](crate::message_interface::Message::MnemonicToHexSeed)
@@ -231,6 +236,9 @@ pub enum Response {
     /// - [`HashTransactionEssence`](crate::message_interface::Message::HashTransactionEssence)
     TransactionEssenceHash(String),
     /// Response for:
+    /// - [`VerifyEd25519Signature`](crate::message_interface::Message::VerifyEd25519Signature)
+    VerifiedEd25519Signature(bool),
+    /// Response for:
     /// - [`ClearListeners`](crate::message_interface::Message::ClearListeners)
     /// - [`StoreMnemonic`](crate::message_interface::Message::StoreMnemonic)
     Ok,
@@ -3,25 +3,50 @@
 
 //! IOTA node core API
 
+pub mod output_cache;
 pub mod routes;
+pub mod stale;
 
+use std::{str::FromStr, time::Duration};
+
+use futures::{Stream, StreamExt};
 use iota_types::{
-    api::core::response::OutputWithMetadataResponse,
-    block::output::{dto::OutputMetadataDto, OutputId},
+    api::core::response::{BlockMetadataResponse, OutputWithMetadataResponse},
+    block::{
+        output::{dto::OutputMetadataDto, OutputId},
+        BlockId,
+    },
 };
 
-#[cfg(not(target_family = "wasm"))]
-use crate::constants::MAX_PARALLEL_API_REQUESTS;
-use crate::{Client, Result};
+use crate::{constants::MAX_PARALLEL_API_REQUESTS, Client, Result};
+
+/// An output created or consumed by a milestone
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum UtxoChange {
+    /// Output created by the milestone
+    Created(OutputId),
+    /// Output consumed by the milestone
+    Consumed(OutputId),
+}
 
 impl Client {
     /// Request outputs by their output ID in parallel
     pub async fn get_outputs(&self, output_ids: Vec<OutputId>) -> Result<Vec<OutputWithMetadataResponse>> {
+        self.get_outputs_with_timeout(output_ids, self.get_timeout()).await
+    }
+
+    /// Request outputs by their output ID in parallel, with the given timeout for each request instead of the API
+    /// timeout of the client
+    pub async fn get_outputs_with_timeout(
+        &self,
+        output_ids: Vec<OutputId>,
+        timeout: Duration,
+    ) -> Result<Vec<OutputWithMetadataResponse>> {
         let mut outputs = Vec::new();
 
         #[cfg(target_family = "wasm")]
         for output_id in output_ids {
-            outputs.push(self.get_output(&output_id).await?);
+            outputs.push(self.get_output_with_timeout(&output_id, timeout).await?);
         }
 
         #[cfg(not(target_family = "wasm"))]
@@ -32,7 +57,7 @@ impl Client {
 
                 tasks.push(async move {
                     tokio::spawn(async move {
-                        let output_response = client_.get_output(&output_id).await?;
+                        let output_response = client_.get_output_with_timeout(&output_id, timeout).await?;
                         crate::Result::Ok(output_response)
                     })
                     .await
@@ -46,6 +71,56 @@ impl Client {
         Ok(outputs)
     }
 
+    /// Request outputs by their output ID with at most `concurrency` requests in flight at once.
+    /// The results are in the same order as the provided output IDs and a failed request doesn't abort the others.
+    pub async fn get_outputs_concurrent(
+        &self,
+        output_ids: &[OutputId],
+        concurrency: usize,
+    ) -> Vec<Result<OutputWithMetadataResponse>> {
+        futures::stream::iter(output_ids)
+            .map(|output_id| self.get_output(output_id))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Returns tips that are ideal for attaching a block together with their metadata, e.g. if and by which milestone
+    /// they are already referenced, so better suited parents can be chosen.
+    pub async fn get_tips_with_metadata(&self) -> Result<Vec<(BlockId, BlockMetadataResponse)>> {
+        let tips = self.get_tips().await?;
+
+        futures::stream::iter(tips)
+            .map(|block_id| async move { Ok((block_id, self.get_block_metadata(&block_id).await?)) })
+            .buffered(MAX_PARALLEL_API_REQUESTS)
+            .collect::<Vec<Result<_>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Stream the UTXO changes of a milestone by its milestone index, first the created and then the consumed outputs.
+    /// Items are yielded one at a time, so the stream can be dropped early if only some changes are needed.
+    pub fn get_utxo_changes_by_index_stream(&self, index: u32) -> impl Stream<Item = Result<UtxoChange>> + '_ {
+        // The node returns all changes at once for now, so they're requested once and yielded from the response.
+        futures::stream::once(self.get_utxo_changes_by_index(index))
+            .map(|response| match response {
+                Ok(response) => {
+                    let created = response
+                        .created_outputs
+                        .into_iter()
+                        .map(|output_id| Ok(UtxoChange::Created(OutputId::from_str(&output_id)?)));
+                    let consumed = response
+                        .consumed_outputs
+                        .into_iter()
+                        .map(|output_id| Ok(UtxoChange::Consumed(OutputId::from_str(&output_id)?)));
+                    futures::stream::iter(created.chain(consumed)).left_stream()
+                }
+                Err(e) => futures::stream::iter(Some(Err(e))).right_stream(),
+            })
+            .flatten()
+    }
+
     /// Request outputs by their output ID in parallel, ignoring failed requests
     /// Useful to get data about spent outputs, that might not be pruned yet
     pub async fn try_get_outputs(&self, output_ids: Vec<OutputId>) -> Result<Vec<OutputWithMetadataResponse>> {
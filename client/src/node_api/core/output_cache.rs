@@ -0,0 +1,103 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-memory cache for the outputs requested by [`Client::get_output()`].
+
+use std::{collections::HashMap, time::Duration};
+
+use instant::Instant;
+use iota_types::{api::core::response::OutputWithMetadataResponse, block::output::OutputId};
+
+use crate::{Client, Error, Result};
+
+/// Options for the output cache of the [`Client`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OutputCacheOptions {
+    /// Maximum amount of cached outputs, the least recently used one is removed when it's exceeded.
+    pub capacity: usize,
+    /// Time after which a cached unspent output is requested again. Spent outputs can't change anymore, so they
+    /// don't expire.
+    pub ttl: Duration,
+}
+
+// A cached output with the time it expires at, if it can still change.
+#[derive(Debug)]
+struct CachedOutput {
+    output: OutputWithMetadataResponse,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+/// Least recently used cache of output responses.
+#[derive(Debug, Default)]
+pub(crate) struct OutputCache {
+    options: Option<OutputCacheOptions>,
+    outputs: HashMap<OutputId, CachedOutput>,
+    // Incremented on every access, to find the least recently used output.
+    uses: u64,
+}
+
+impl OutputCache {
+    pub(crate) fn new(options: Option<OutputCacheOptions>) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn options(&self) -> Option<OutputCacheOptions> {
+        self.options
+    }
+
+    pub(crate) fn get(&mut self, output_id: &OutputId) -> Option<OutputWithMetadataResponse> {
+        self.uses += 1;
+        let cached = self.outputs.get_mut(output_id)?;
+        if matches!(cached.expires_at, Some(expires_at) if expires_at <= Instant::now()) {
+            self.outputs.remove(output_id);
+            return None;
+        }
+        cached.last_used = self.uses;
+
+        Some(cached.output.clone())
+    }
+
+    pub(crate) fn insert(&mut self, output_id: OutputId, output: OutputWithMetadataResponse) {
+        let options = match self.options {
+            Some(options) if options.capacity > 0 => options,
+            _ => return,
+        };
+        if self.outputs.len() >= options.capacity && !self.outputs.contains_key(&output_id) {
+            let least_recently_used = self
+                .outputs
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(output_id, _)| *output_id);
+            if let Some(least_recently_used) = least_recently_used {
+                self.outputs.remove(&least_recently_used);
+            }
+        }
+
+        self.uses += 1;
+        let expires_at = (!output.metadata.is_spent).then(|| Instant::now() + options.ttl);
+        self.outputs.insert(
+            output_id,
+            CachedOutput {
+                output,
+                expires_at,
+                last_used: self.uses,
+            },
+        );
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.outputs.clear();
+    }
+}
+
+impl Client {
+    /// Removes all outputs from the output cache.
+    pub fn clear_output_cache(&self) -> Result<()> {
+        self.output_cache.lock().map_err(|_| Error::PoisonError)?.clear();
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@
 
 //! Node core API routes.
 
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use iota_types::{
     api::core::{
@@ -27,13 +27,17 @@ use packable::PackableExt;
 use url::Url;
 
 use crate::{
+    builder::BlockEncoding,
     constants::{DEFAULT_API_TIMEOUT, DEFAULT_USER_AGENT},
-    node_manager::node::{Node, NodeAuth},
+    node_manager::{
+        http_client::HttpClient,
+        node::{Node, NodeAuth},
+    },
     Client, Error, Result,
 };
 
 /// NodeInfo wrapper which contains the node info and the url from the node (useful when multiple nodes are used)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodeInfoWrapper {
     /// The returned node info
     #[serde(rename = "nodeInfo")]
@@ -42,6 +46,33 @@ pub struct NodeInfoWrapper {
     pub url: String,
 }
 
+// Only the id of a block metadata response, the other fields are skipped when deserializing.
+#[derive(Debug, Serialize, Deserialize)]
+struct BlockIdResponse {
+    #[serde(rename = "blockId")]
+    block_id: String,
+}
+
+// Whether a remote PoW request failed because no node could do the PoW, hornet and bee return different errors.
+fn is_remote_pow_unavailable(error: &str) -> bool {
+    error == "no available nodes with remote Pow"
+        || error.contains("proof of work is not enabled")
+        || error.contains("`Pow` not enabled")
+}
+
+// Blocks built with the `BlockBuilder` can't exceed the max length, but deserialized ones aren't checked, so we check
+// them before posting instead of getting an error from the node.
+fn check_block_length(block: &Block) -> Result<()> {
+    let size = block.packed_len();
+    if size > Block::LENGTH_MAX {
+        return Err(Error::BlockTooLarge {
+            size,
+            max: Block::LENGTH_MAX,
+        });
+    }
+    Ok(())
+}
+
 impl Client {
     // Node routes.
 
@@ -52,7 +83,9 @@ impl Client {
 
         let mut url = Url::parse(url)?;
         url.set_path(path);
-        let status = crate::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
+        let status = self
+            .node_manager
+            .http_client
             .get(
                 Node {
                     url,
@@ -83,15 +116,36 @@ impl Client {
     /// Returns general information about the node.
     /// GET /api/core/v2/info
     pub async fn get_info(&self) -> Result<NodeInfoWrapper> {
+        let (node_info, url) = self.get_info_with_url().await?;
+
+        Ok(NodeInfoWrapper {
+            node_info,
+            url: url.origin().ascii_serialization(),
+        })
+    }
+
+    /// Returns general information about the node and the url of the node that returned it, useful to send follow-up
+    /// requests to the same node. The url doesn't contain the credentials of the node.
+    /// GET /api/core/v2/info
+    pub async fn get_info_with_url(&self) -> Result<(InfoResponse, Url)> {
         let path = "api/core/v2/info";
 
         self.node_manager
-            .get_request(path, None, self.get_timeout(), false, false)
+            .get_request_with_url(path, self.get_timeout())
             .await
     }
 
     /// GET /api/core/v2/info endpoint
     pub async fn get_node_info(url: &str, auth: Option<NodeAuth>) -> Result<InfoResponse> {
+        Self::get_node_info_with_http_client(&HttpClient::new(DEFAULT_USER_AGENT.to_string()), url, auth).await
+    }
+
+    // Requests the info of a node with an existing HTTP client, so its open connections can be reused.
+    pub(crate) async fn get_node_info_with_http_client(
+        http_client: &HttpClient,
+        url: &str,
+        auth: Option<NodeAuth>,
+    ) -> Result<InfoResponse> {
         let mut url = crate::node_manager::builder::validate_url(Url::parse(url)?)?;
         if let Some(auth) = &auth {
             if let Some((name, password)) = &auth.basic_auth_name_pwd {
@@ -103,7 +157,7 @@ impl Client {
         let path = "api/core/v2/info";
         url.set_path(path);
 
-        let resp: InfoResponse = crate::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
+        let resp: InfoResponse = http_client
             .get(
                 Node {
                     url,
@@ -139,6 +193,23 @@ impl Client {
 
     // Blocks routes.
 
+    /// Returns the BlockId of the submitted block.
+    /// Posts the block with the encoding set with
+    /// [`ClientBuilder::with_block_encoding()`](crate::ClientBuilder::with_block_encoding()).
+    pub async fn submit_block(&self, block: &Block) -> Result<BlockId> {
+        match self.block_encoding {
+            BlockEncoding::Raw => self.post_block_raw(block).await,
+            BlockEncoding::Json => self.post_block(block).await,
+            BlockEncoding::Auto(raw_threshold) => {
+                if block.packed_len() > raw_threshold {
+                    self.post_block_raw(block).await
+                } else {
+                    self.post_block(block).await
+                }
+            }
+        }
+    }
+
     /// Returns the BlockId of the submitted block.
     /// POST JSON to /api/core/v2/blocks
     pub async fn post_block(&self, block: &Block) -> Result<BlockId> {
@@ -149,6 +220,7 @@ impl Client {
         } else {
             self.get_remote_pow_timeout()
         };
+        check_block_length(block)?;
         let block_dto = BlockDto::from(block);
 
         // fallback to local PoW if remote PoW fails
@@ -160,39 +232,10 @@ impl Client {
             Ok(res) => res,
             Err(e) => {
                 if let Error::Node(e) = e {
-                    let fallback_to_local_pow = self.get_fallback_to_local_pow();
-                    // hornet and bee return different error blocks
-                    if (e == *"No available nodes with remote Pow"
-                        || e.contains("proof of work is not enabled")
-                        || e.contains("`Pow` not enabled"))
-                        && fallback_to_local_pow
-                    {
-                        // Without this we get:within `impl Future<Output = [async output]>`, the trait `Send` is not
-                        // implemented for `std::sync::RwLockWriteGuard<'_, NetworkInfo>`
-                        {
-                            let mut client_network_info =
-                                self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
-                            // switch to local PoW
-                            client_network_info.local_pow = true;
-                        }
-                        let block_res = self.finish_block_builder(None, block.payload().cloned()).await;
-                        let block_with_local_pow = match block_res {
-                            Ok(block) => {
-                                // reset local PoW state
-                                let mut client_network_info =
-                                    self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
-                                client_network_info.local_pow = false;
-                                block
-                            }
-                            Err(e) => {
-                                // reset local PoW state
-                                self.network_info
-                                    .write()
-                                    .map_err(|_| crate::Error::PoisonError)?
-                                    .local_pow = false;
-                                return Err(e);
-                            }
-                        };
+                    if !local_pow && is_remote_pow_unavailable(&e) && self.get_fallback_to_local_pow() {
+                        // Do the PoW locally instead of switching the client to local PoW, so concurrent requests
+                        // aren't affected.
+                        let block_with_local_pow = self.finish_pow(None, block.payload().cloned()).await?;
                         let block_dto = BlockDto::from(&block_with_local_pow);
 
                         self.node_manager
@@ -220,6 +263,7 @@ impl Client {
         } else {
             self.get_remote_pow_timeout()
         };
+        check_block_length(block)?;
 
         // fallback to local Pow if remote Pow fails
         let resp = match self
@@ -230,39 +274,10 @@ impl Client {
             Ok(res) => res,
             Err(e) => {
                 if let Error::Node(e) = e {
-                    let fallback_to_local_pow = self.get_fallback_to_local_pow();
-                    // hornet and bee return different error blocks
-                    if (e == *"No available nodes with remote Pow"
-                        || e.contains("proof of work is not enabled")
-                        || e.contains("`Pow` not enabled"))
-                        && fallback_to_local_pow
-                    {
-                        // Without this we get:within `impl Future<Output = [async output]>`, the trait `Send` is not
-                        // implemented for `std::sync::RwLockWriteGuard<'_, NetworkInfo>`
-                        {
-                            let mut client_network_info =
-                                self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
-                            // switch to local PoW
-                            client_network_info.local_pow = true;
-                        }
-                        let block_res = self.finish_block_builder(None, block.payload().cloned()).await;
-                        let block_with_local_pow = match block_res {
-                            Ok(block) => {
-                                // reset local PoW state
-                                let mut client_network_info =
-                                    self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
-                                client_network_info.local_pow = false;
-                                block
-                            }
-                            Err(e) => {
-                                // reset local PoW state
-                                self.network_info
-                                    .write()
-                                    .map_err(|_| crate::Error::PoisonError)?
-                                    .local_pow = false;
-                                return Err(e);
-                            }
-                        };
+                    if !local_pow && is_remote_pow_unavailable(&e) && self.get_fallback_to_local_pow() {
+                        // Do the PoW locally instead of switching the client to local PoW, so concurrent requests
+                        // aren't affected.
+                        let block_with_local_pow = self.finish_pow(None, block.payload().cloned()).await?;
                         self.node_manager
                             .post_request_bytes(path, timeout, &block_with_local_pow.pack_to_vec(), true)
                             .await?
@@ -319,11 +334,30 @@ impl Client {
     /// Finds an output, as JSON, by its OutputId (TransactionId + output_index).
     /// GET /api/core/v2/outputs/{outputId}
     pub async fn get_output(&self, output_id: &OutputId) -> Result<OutputWithMetadataResponse> {
+        self.get_output_with_timeout(output_id, self.get_timeout()).await
+    }
+
+    /// Finds an output, as JSON, by its OutputId (TransactionId + output_index), with the given timeout instead of the
+    /// API timeout of the client.
+    /// GET /api/core/v2/outputs/{outputId}
+    pub async fn get_output_with_timeout(
+        &self,
+        output_id: &OutputId,
+        timeout: Duration,
+    ) -> Result<OutputWithMetadataResponse> {
+        if let Some(output) = self.output_cache.lock().map_err(|_| Error::PoisonError)?.get(output_id) {
+            return Ok(output);
+        }
         let path = &format!("api/core/v2/outputs/{output_id}");
 
-        self.node_manager
-            .get_request(path, None, self.get_timeout(), false, true)
-            .await
+        let output: OutputWithMetadataResponse =
+            self.node_manager.get_request(path, None, timeout, false, true).await?;
+        self.output_cache
+            .lock()
+            .map_err(|_| Error::PoisonError)?
+            .insert(*output_id, output.clone());
+
+        Ok(output)
     }
 
     /// Finds an output, as raw bytes, by its OutputId (TransactionId + output_index).
@@ -419,6 +453,20 @@ impl Client {
             .await
     }
 
+    /// Returns the id of the block that was included in the ledger for a given TransactionId, without requesting
+    /// the block itself.
+    /// GET /api/core/v2/transactions/{transactionId}/included-block/metadata
+    pub async fn get_included_block_id(&self, transaction_id: &TransactionId) -> Result<BlockId> {
+        let path = &format!("api/core/v2/transactions/{transaction_id}/included-block/metadata");
+
+        let resp = self
+            .node_manager
+            .get_request::<BlockIdResponse>(path, None, self.get_timeout(), true, true)
+            .await?;
+
+        Ok(BlockId::from_str(&resp.block_id)?)
+    }
+
     // Milestones routes.
 
     /// Gets the milestone by the given milestone id.
@@ -460,8 +508,8 @@ impl Client {
             .await
     }
 
-    /// Gets the milestone by the given milestone index.
-    /// GET /api/core/v2/milestones/{index}
+    /// Gets the full milestone payload, including its merkle roots and signatures, by the given milestone index.
+    /// GET /api/core/v2/milestones/by-index/{index}
     pub async fn get_milestone_by_index(&self, index: u32) -> Result<MilestonePayload> {
         let path = &format!("api/core/v2/milestones/by-index/{index}");
 
@@ -0,0 +1,146 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read methods which fall back to the last successful result when no node is reachable.
+
+use std::{collections::HashMap, hash::Hash};
+
+use iota_types::{
+    api::core::response::OutputWithMetadataResponse,
+    block::{
+        output::OutputId,
+        payload::milestone::{MilestoneId, MilestonePayload},
+    },
+};
+
+use crate::{constants::STALE_CACHE_MAX_ENTRIES, node_api::core::routes::NodeInfoWrapper, Client, Error, Result};
+
+/// A result which is either fresh from a node or the last one that was received, if no node was reachable.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MaybeStale<T> {
+    /// The result.
+    pub value: T,
+    /// Whether the result comes from the cache because no node was reachable.
+    pub stale: bool,
+}
+
+/// The last successful results of the `*_or_stale` methods of the [`Client`].
+#[derive(Debug, Default)]
+pub(crate) struct StaleCache {
+    info: Option<NodeInfoWrapper>,
+    outputs: HashMap<OutputId, OutputWithMetadataResponse>,
+    milestones: HashMap<MilestoneId, MilestonePayload>,
+}
+
+// Inserts a value, removing an arbitrary entry first if the map is full.
+fn insert_bounded<K: Eq + Hash + Clone, V>(map: &mut HashMap<K, V>, key: K, value: V) {
+    if map.len() >= STALE_CACHE_MAX_ENTRIES && !map.contains_key(&key) {
+        if let Some(evicted) = map.keys().next().cloned() {
+            map.remove(&evicted);
+        }
+    }
+    map.insert(key, value);
+}
+
+// Only errors that show that no node could be reached trigger the fallback, errors returned by a node don't.
+fn is_unreachable(error: &Error) -> bool {
+    matches!(error, Error::HealthyNodePoolEmpty | Error::Reqwest(_))
+}
+
+// Returns the fresh result, or the cached one if no node was reachable.
+fn fresh_or_stale<T>(result: Result<T>, cached: impl FnOnce() -> Option<T>) -> Result<MaybeStale<T>> {
+    match result {
+        Ok(value) => Ok(MaybeStale { value, stale: false }),
+        Err(error) if is_unreachable(&error) => match cached() {
+            Some(value) => {
+                log::warn!("[get_or_stale] no node reachable, returning the cached result: {error}");
+                Ok(MaybeStale { value, stale: true })
+            }
+            None => Err(error),
+        },
+        Err(error) => Err(error),
+    }
+}
+
+impl Client {
+    /// Like [`Client::get_info()`], but returns the last node info received by this method, marked as stale, if no
+    /// node is reachable.
+    pub async fn get_info_or_stale(&self) -> Result<MaybeStale<NodeInfoWrapper>> {
+        let result = self.get_info().await;
+        if let Ok(info) = &result {
+            self.stale_cache.write().map_err(|_| Error::PoisonError)?.info = Some(info.clone());
+        }
+
+        fresh_or_stale(result, || {
+            self.stale_cache.read().ok().and_then(|cache| cache.info.clone())
+        })
+    }
+
+    /// Like [`Client::get_output()`], but returns the last response for the output received by this method, marked as
+    /// stale, if no node is reachable.
+    pub async fn get_output_or_stale(&self, output_id: &OutputId) -> Result<MaybeStale<OutputWithMetadataResponse>> {
+        let result = self.get_output(output_id).await;
+        if let Ok(output) = &result {
+            insert_bounded(
+                &mut self.stale_cache.write().map_err(|_| Error::PoisonError)?.outputs,
+                *output_id,
+                output.clone(),
+            );
+        }
+
+        fresh_or_stale(result, || {
+            self.stale_cache
+                .read()
+                .ok()
+                .and_then(|cache| cache.outputs.get(output_id).cloned())
+        })
+    }
+
+    /// Like [`Client::get_milestone_by_id()`], but returns the last milestone received by this method or
+    /// [`Client::get_milestone_by_index_or_stale()`], marked as stale, if no node is reachable.
+    pub async fn get_milestone_by_id_or_stale(
+        &self,
+        milestone_id: &MilestoneId,
+    ) -> Result<MaybeStale<MilestonePayload>> {
+        let result = self.get_milestone_by_id(milestone_id).await;
+        if let Ok(milestone) = &result {
+            self.cache_milestone(milestone)?;
+        }
+
+        fresh_or_stale(result, || {
+            self.stale_cache
+                .read()
+                .ok()
+                .and_then(|cache| cache.milestones.get(milestone_id).cloned())
+        })
+    }
+
+    /// Like [`Client::get_milestone_by_index()`], but returns the last milestone received by this method or
+    /// [`Client::get_milestone_by_id_or_stale()`], marked as stale, if no node is reachable.
+    pub async fn get_milestone_by_index_or_stale(&self, index: u32) -> Result<MaybeStale<MilestonePayload>> {
+        let result = self.get_milestone_by_index(index).await;
+        if let Ok(milestone) = &result {
+            self.cache_milestone(milestone)?;
+        }
+
+        fresh_or_stale(result, || {
+            self.stale_cache.read().ok().and_then(|cache| {
+                cache
+                    .milestones
+                    .values()
+                    .find(|milestone| *milestone.essence().index() == index)
+                    .cloned()
+            })
+        })
+    }
+
+    fn cache_milestone(&self, milestone: &MilestonePayload) -> Result<()> {
+        insert_bounded(
+            &mut self.stale_cache.write().map_err(|_| Error::PoisonError)?.milestones,
+            milestone.id(),
+            milestone.clone(),
+        );
+
+        Ok(())
+    }
+}
@@ -32,14 +32,7 @@ impl Client {
 
         while let Some(cursor) = {
             let output_ids_response = self
-                .node_manager
-                .get_request::<OutputIdsResponse>(
-                    route,
-                    query_parameters.to_query_string().as_deref(),
-                    self.get_timeout(),
-                    need_quorum,
-                    prefer_permanode,
-                )
+                .get_output_ids_page(route, &query_parameters, need_quorum, prefer_permanode)
                 .await?;
 
             if return_early {
@@ -57,4 +50,23 @@ impl Client {
 
         Ok(merged_output_ids_response)
     }
+
+    /// Get a single page of output ids for a provided URL route and query parameters.
+    pub(crate) async fn get_output_ids_page(
+        &self,
+        route: &str,
+        query_parameters: &QueryParameters,
+        need_quorum: bool,
+        prefer_permanode: bool,
+    ) -> Result<OutputIdsResponse> {
+        self.node_manager
+            .get_request::<OutputIdsResponse>(
+                route,
+                query_parameters.to_query_string().as_deref(),
+                self.get_timeout(),
+                need_quorum,
+                prefer_permanode,
+            )
+            .await
+    }
 }
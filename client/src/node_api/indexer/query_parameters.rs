@@ -68,6 +68,10 @@ pub enum QueryParameter {
     Address(String),
     /// Filter foundry outputs based on bech32-encoded address of the controlling alias.
     AliasAddress(String),
+    /// Returns outputs with an amount greater than the given one.
+    AmountGreaterThan(u64),
+    /// Returns outputs with an amount less than the given one.
+    AmountLessThan(u64),
     /// Returns outputs that were created after a certain Unix timestamp.
     CreatedAfter(u32),
     /// Returns outputs that were created before a certain Unix timestamp.
@@ -120,6 +124,8 @@ impl QueryParameter {
         match self {
             Self::Address(v) => format!("address={v}"),
             Self::AliasAddress(v) => format!("aliasAddress={v}"),
+            Self::AmountGreaterThan(v) => format!("amountGreaterThan={v}"),
+            Self::AmountLessThan(v) => format!("amountLessThan={v}"),
             Self::CreatedAfter(v) => format!("createdAfter={v}"),
             Self::CreatedBefore(v) => format!("createdBefore={v}"),
             Self::Cursor(v) => format!("cursor={v}"),
@@ -148,27 +154,29 @@ impl QueryParameter {
         match self {
             Self::Address(_) => 0,
             Self::AliasAddress(_) => 1,
-            Self::CreatedAfter(_) => 2,
-            Self::CreatedBefore(_) => 3,
-            Self::Cursor(_) => 4,
-            Self::ExpirationReturnAddress(_) => 5,
-            Self::ExpiresAfter(_) => 6,
-            Self::ExpiresBefore(_) => 7,
-            Self::Governor(_) => 8,
-            Self::HasExpiration(_) => 9,
-            Self::HasNativeTokens(_) => 10,
-            Self::HasStorageDepositReturn(_) => 11,
-            Self::HasTimelock(_) => 12,
-            Self::Issuer(_) => 13,
-            Self::MaxNativeTokenCount(_) => 14,
-            Self::MinNativeTokenCount(_) => 15,
-            Self::PageSize(_) => 16,
-            Self::Sender(_) => 17,
-            Self::StateController(_) => 18,
-            Self::StorageDepositReturnAddress(_) => 19,
-            Self::Tag(_) => 20,
-            Self::TimelockedAfter(_) => 21,
-            Self::TimelockedBefore(_) => 22,
+            Self::AmountGreaterThan(_) => 2,
+            Self::AmountLessThan(_) => 3,
+            Self::CreatedAfter(_) => 4,
+            Self::CreatedBefore(_) => 5,
+            Self::Cursor(_) => 6,
+            Self::ExpirationReturnAddress(_) => 7,
+            Self::ExpiresAfter(_) => 8,
+            Self::ExpiresBefore(_) => 9,
+            Self::Governor(_) => 10,
+            Self::HasExpiration(_) => 11,
+            Self::HasNativeTokens(_) => 12,
+            Self::HasStorageDepositReturn(_) => 13,
+            Self::HasTimelock(_) => 14,
+            Self::Issuer(_) => 15,
+            Self::MaxNativeTokenCount(_) => 16,
+            Self::MinNativeTokenCount(_) => 17,
+            Self::PageSize(_) => 18,
+            Self::Sender(_) => 19,
+            Self::StateController(_) => 20,
+            Self::StorageDepositReturnAddress(_) => 21,
+            Self::Tag(_) => 22,
+            Self::TimelockedAfter(_) => 23,
+            Self::TimelockedBefore(_) => 24,
         }
     }
 }
@@ -191,10 +199,32 @@ macro_rules! verify_query_parameters {
     };
 }
 
+// Rejects amount filters that no output can match.
+fn verify_amount_range(query_parameters: &[QueryParameter]) -> Result<()> {
+    let greater_than = query_parameters.iter().find_map(|qp| match qp {
+        QueryParameter::AmountGreaterThan(amount) => Some(*amount),
+        _ => None,
+    });
+    let less_than = query_parameters.iter().find_map(|qp| match qp {
+        QueryParameter::AmountLessThan(amount) => Some(*amount),
+        _ => None,
+    });
+
+    match (greater_than, less_than) {
+        (Some(greater_than), Some(less_than)) if greater_than >= less_than => Err(Error::InvalidAmountRange {
+            greater_than,
+            less_than,
+        }),
+        _ => Ok(()),
+    }
+}
+
 pub(crate) fn verify_query_parameters_basic_outputs(query_parameters: Vec<QueryParameter>) -> Result<QueryParameters> {
     verify_query_parameters!(
         query_parameters,
         QueryParameter::Address,
+        QueryParameter::AmountGreaterThan,
+        QueryParameter::AmountLessThan,
         QueryParameter::HasNativeTokens,
         QueryParameter::MinNativeTokenCount,
         QueryParameter::MaxNativeTokenCount,
@@ -214,6 +244,7 @@ pub(crate) fn verify_query_parameters_basic_outputs(query_parameters: Vec<QueryP
         QueryParameter::PageSize,
         QueryParameter::Cursor
     )?;
+    verify_amount_range(&query_parameters)?;
 
     Ok(QueryParameters::new(query_parameters))
 }
@@ -225,6 +256,8 @@ pub(crate) fn verify_query_parameters_alias_outputs(query_parameters: Vec<QueryP
         QueryParameter::Governor,
         QueryParameter::Issuer,
         QueryParameter::Sender,
+        QueryParameter::AmountGreaterThan,
+        QueryParameter::AmountLessThan,
         QueryParameter::HasNativeTokens,
         QueryParameter::MinNativeTokenCount,
         QueryParameter::MaxNativeTokenCount,
@@ -233,6 +266,7 @@ pub(crate) fn verify_query_parameters_alias_outputs(query_parameters: Vec<QueryP
         QueryParameter::PageSize,
         QueryParameter::Cursor
     )?;
+    verify_amount_range(&query_parameters)?;
 
     Ok(QueryParameters::new(query_parameters))
 }
@@ -243,6 +277,8 @@ pub(crate) fn verify_query_parameters_foundry_outputs(
     verify_query_parameters!(
         query_parameters,
         QueryParameter::AliasAddress,
+        QueryParameter::AmountGreaterThan,
+        QueryParameter::AmountLessThan,
         QueryParameter::HasNativeTokens,
         QueryParameter::MinNativeTokenCount,
         QueryParameter::MaxNativeTokenCount,
@@ -251,6 +287,7 @@ pub(crate) fn verify_query_parameters_foundry_outputs(
         QueryParameter::PageSize,
         QueryParameter::Cursor
     )?;
+    verify_amount_range(&query_parameters)?;
 
     Ok(QueryParameters::new(query_parameters))
 }
@@ -259,6 +296,8 @@ pub(crate) fn verify_query_parameters_nft_outputs(query_parameters: Vec<QueryPar
     verify_query_parameters!(
         query_parameters,
         QueryParameter::Address,
+        QueryParameter::AmountGreaterThan,
+        QueryParameter::AmountLessThan,
         QueryParameter::HasNativeTokens,
         QueryParameter::MinNativeTokenCount,
         QueryParameter::MaxNativeTokenCount,
@@ -278,6 +317,7 @@ pub(crate) fn verify_query_parameters_nft_outputs(query_parameters: Vec<QueryPar
         QueryParameter::PageSize,
         QueryParameter::Cursor
     )?;
+    verify_amount_range(&query_parameters)?;
 
     Ok(QueryParameters::new(query_parameters))
 }
@@ -308,4 +348,30 @@ mod tests {
         // Contains no cursor query parameter
         assert!(!query_parameters.contains(QueryParameter::Cursor(String::new()).kind()));
     }
+
+    #[test]
+    fn amount_query_parameters() {
+        let query_parameters = verify_query_parameters_basic_outputs(vec![
+            QueryParameter::AmountLessThan(2_000_000),
+            QueryParameter::HasNativeTokens(false),
+            QueryParameter::Address("atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r".into()),
+            QueryParameter::AmountGreaterThan(1_000_000),
+        ])
+        .unwrap();
+        assert_eq!(
+            query_parameters.to_query_string().unwrap(),
+            "address=atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r&amountGreaterThan=1000000&amountLessThan=2000000&hasNativeTokens=false"
+        );
+
+        assert!(matches!(
+            verify_query_parameters_basic_outputs(vec![
+                QueryParameter::AmountGreaterThan(2_000_000),
+                QueryParameter::AmountLessThan(2_000_000),
+            ]),
+            Err(Error::InvalidAmountRange {
+                greater_than: 2_000_000,
+                less_than: 2_000_000
+            })
+        ));
+    }
 }
@@ -16,7 +16,7 @@ use crate::{
         },
         QueryParameters,
     },
-    Client, Result,
+    Client, Error, Result,
 };
 
 // hornet: https://github.com/gohornet/hornet/blob/develop/plugins/indexer/routes.go
@@ -36,6 +36,52 @@ impl Client {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get a single page of basic output ids filtered by the given parameters, starting at the given cursor or at
+    /// the first page. Returns the output ids of the page and the cursor of the next page, if there is one, so that
+    /// large result sets can be processed page by page. [`Client::basic_output_ids`] follows all cursors instead.
+    /// api/indexer/v1/outputs/basic
+    pub async fn basic_output_ids_paged(
+        &self,
+        query_parameters: Vec<QueryParameter>,
+        cursor: Option<String>,
+    ) -> Result<(Vec<OutputId>, Option<String>)> {
+        let route = "api/indexer/v1/outputs/basic";
+
+        let mut query_parameters = verify_query_parameters_basic_outputs(query_parameters)?;
+        if let Some(cursor) = cursor {
+            query_parameters.replace(QueryParameter::Cursor(cursor));
+        }
+
+        let output_ids_response = self.get_output_ids_page(route, &query_parameters, true, false).await?;
+
+        Ok((output_ids_response.items, output_ids_response.cursor))
+    }
+
+    /// Get all basic output ids filtered by the given parameters, following the cursors of at most `max_pages` pages.
+    /// Unlike [`Client::basic_output_ids`], which follows all cursors, it returns [`Error::OutputIdsPageLimit`] if the
+    /// indexer still has more pages after that, so the result is never silently truncated.
+    /// api/indexer/v1/outputs/basic
+    pub async fn basic_output_ids_all(
+        &self,
+        query_parameters: Vec<QueryParameter>,
+        max_pages: usize,
+    ) -> Result<Vec<OutputId>> {
+        let mut output_ids = Vec::new();
+        let mut cursor = None;
+
+        for _ in 0..max_pages {
+            let (page, next_cursor) = self.basic_output_ids_paged(query_parameters.clone(), cursor).await?;
+            output_ids.extend(page);
+
+            match next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return Ok(output_ids),
+            }
+        }
+
+        Err(Error::OutputIdsPageLimit { max_pages })
+    }
+
     /// Get alias outputs filtered by the given parameters.
     /// GET with query parameter returns all outputIDs that fit these filter criteria.
     /// Query parameters: "stateController", "governor", "issuer", "sender", "createdBefore", "createdAfter"
@@ -61,7 +61,7 @@ async fn set_mqtt_client(client: &Client) -> Result<(), Error> {
     let exists = client.mqtt_client.read().await.is_some();
 
     if !exists {
-        let nodes = if !client.node_manager.ignore_node_health {
+        let nodes = if !client.node_manager.ignore_node_health && !client.node_manager.node_sync_disabled {
             #[cfg(not(target_family = "wasm"))]
             {
                 client
@@ -227,6 +227,28 @@ fn poll_mqtt(
                                                 Err(())
                                             }
                                         }
+                                    } else if topic.starts_with("block-metadata/") {
+                                        match serde_json::from_slice(&p.payload) {
+                                            Ok(metadata) => Ok(TopicEvent {
+                                                topic,
+                                                payload: MqttPayload::BlockMetadata(metadata),
+                                            }),
+                                            Err(e) => {
+                                                warn!("Cannot parse block metadata: {:?}", e);
+                                                Err(())
+                                            }
+                                        }
+                                    } else if topic.starts_with("outputs/") {
+                                        match serde_json::from_slice(&p.payload) {
+                                            Ok(output) => Ok(TopicEvent {
+                                                topic,
+                                                payload: MqttPayload::Output(output),
+                                            }),
+                                            Err(e) => {
+                                                warn!("Cannot parse output: {:?}", e);
+                                                Err(())
+                                            }
+                                        }
                                     } else {
                                         match serde_json::from_slice(&p.payload) {
                                             Ok(value) => Ok(TopicEvent {
@@ -256,6 +278,16 @@ fn poll_mqtt(
                         }
                         if connection_failure_count == options.max_reconnection_attempts {
                             let _ = event_sender.send(MqttEvent::Disconnected);
+                            // Notify the handlers, since they won't receive any further events for their topics.
+                            for (topic, handlers) in mqtt_topic_handlers_guard.read().await.iter() {
+                                let event = TopicEvent {
+                                    topic: topic.topic().to_string(),
+                                    payload: MqttPayload::Disconnected,
+                                };
+                                for handler in handlers {
+                                    handler(&event);
+                                }
+                            }
                             break;
                         }
                         error_instant = Instant::now();
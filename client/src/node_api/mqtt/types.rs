@@ -5,9 +5,12 @@
 
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use iota_types::block::{
-    payload::{milestone::ReceiptMilestoneOption, MilestonePayload},
-    Block,
+use iota_types::{
+    api::core::response::{BlockMetadataResponse, OutputWithMetadataResponse},
+    block::{
+        payload::{milestone::ReceiptMilestoneOption, MilestonePayload},
+        Block,
+    },
 };
 use regex::RegexSet;
 use serde::{de::Error as _, Deserialize, Deserializer};
@@ -33,14 +36,20 @@ pub struct TopicEvent {
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum MqttPayload {
-    /// In case it contains JSON.
+    /// In case it contains JSON of a topic without a dedicated type.
     Json(Value),
+    /// In case it contains the metadata of a block.
+    BlockMetadata(BlockMetadataResponse),
+    /// In case it contains an output with its metadata.
+    Output(OutputWithMetadataResponse),
     /// In case it contains a `Block` object.
     Block(Block),
     /// In case it contains a `Milestone` object.
     MilestonePayload(MilestonePayload),
     /// In case it contains a `Receipt` object.
     Receipt(ReceiptMilestoneOption),
+    /// In case the connection to the broker was lost and couldn't be reestablished, no further events follow.
+    Disconnected,
 }
 
 /// Mqtt events.
@@ -199,6 +208,14 @@ impl Topic {
         }
     }
 
+    /// Creates the topic for the outputs unlockable by the given bech32 address through an address unlock condition,
+    /// or for the spent ones of these outputs.
+    pub fn outputs_by_address(bech32_address: &str, spent: bool) -> Result<Self, Error> {
+        let topic = format!("outputs/unlock/address/{bech32_address}");
+
+        Self::try_new(if spent { topic + "/spent" } else { topic })
+    }
+
     /// Creates a new topic without checking if the given string represents a valid topic.
     pub fn new_unchecked(value: String) -> Self {
         Self(value)
@@ -5,7 +5,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    sync::{atomic::AtomicUsize, Arc, RwLock},
     time::Duration,
 };
 
@@ -14,7 +14,10 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
-    constants::{DEFAULT_MIN_QUORUM_SIZE, DEFAULT_QUORUM_THRESHOLD, DEFAULT_USER_AGENT, NODE_SYNC_INTERVAL},
+    constants::{
+        DEFAULT_MAX_MILESTONE_LAG, DEFAULT_MIN_QUORUM_SIZE, DEFAULT_QUORUM_THRESHOLD, DEFAULT_SYNC_CONCURRENCY,
+        DEFAULT_USER_AGENT, NODE_SYNC_INTERVAL,
+    },
     error::{Error, Result},
     node_manager::{
         http_client::HttpClient,
@@ -40,10 +43,20 @@ pub struct NodeManagerBuilder {
     /// If the node health should be ignored
     #[serde(rename = "ignoreNodeHealth", default)]
     pub ignore_node_health: bool,
+    /// If the nodes shouldn't be synced, every node is then considered synced and ready to use
+    #[serde(rename = "nodeSyncDisabled", default)]
+    pub node_sync_disabled: bool,
     /// Interval in which nodes will be checked for their sync status and the [NetworkInfo](crate::NetworkInfo)
     /// gets updated
     #[serde(rename = "nodeSyncInterval", default = "default_node_sync_interval")]
     pub node_sync_interval: Duration,
+    /// Maximum amount of nodes that are requested at once while syncing the nodes
+    #[serde(rename = "syncConcurrency", default = "default_sync_concurrency")]
+    pub sync_concurrency: usize,
+    /// Maximum amount of milestones the latest milestone of a node can lag behind the most recent one of the other
+    /// nodes, for the node to still be used
+    #[serde(rename = "maxMilestoneLag", default = "default_max_milestone_lag")]
+    pub max_milestone_lag: u32,
     /// If node quorum is enabled. Will compare the responses from multiple nodes and only returns the response if
     /// `quorum_threshold`% of the nodes return the same one
     #[serde(default)]
@@ -57,6 +70,41 @@ pub struct NodeManagerBuilder {
     /// The User-Agent header for requests
     #[serde(rename = "userAgent", default = "default_user_agent")]
     pub user_agent: String,
+    /// Policy for retrying requests that failed because of a transient error
+    #[serde(rename = "httpRetry", default)]
+    pub http_retry: RetryPolicy,
+    /// Maximum amount of idle connections kept open per node, unlimited if not set
+    #[serde(rename = "poolMaxIdlePerHost", default)]
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Time after which idle connections get closed, 90 seconds if not set
+    #[serde(rename = "poolIdleTimeout", default)]
+    pub pool_idle_timeout: Option<Duration>,
+}
+
+/// Policy for retrying requests that failed with a server error (5xx) or because of a connection error. Client
+/// errors (4xx) are never retried.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum amount of retries after the first attempt, 0 disables retrying
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u32,
+    /// Delay before the first retry
+    #[serde(rename = "baseDelay")]
+    pub base_delay: Duration,
+    /// Factor the delay gets multiplied with for every further retry
+    #[serde(rename = "backoffFactor")]
+    pub backoff_factor: u32,
+    /// If POST requests should be retried too, which might lead to them being processed multiple times by the node
+    #[serde(rename = "retryPost", default)]
+    pub retry_post: bool,
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the retry following the given amount of already done retries.
+    pub fn delay(&self, retries: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(self.backoff_factor.saturating_pow(retries))
+    }
 }
 
 fn default_user_agent() -> String {
@@ -67,6 +115,14 @@ fn default_node_sync_interval() -> Duration {
     NODE_SYNC_INTERVAL
 }
 
+fn default_sync_concurrency() -> usize {
+    DEFAULT_SYNC_CONCURRENCY
+}
+
+fn default_max_milestone_lag() -> u32 {
+    DEFAULT_MAX_MILESTONE_LAG
+}
+
 fn default_min_quorum_size() -> usize {
     DEFAULT_MIN_QUORUM_SIZE
 }
@@ -159,6 +215,11 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_node_sync_disabled(mut self) -> Self {
+        self.node_sync_disabled = true;
+        self
+    }
+
     pub(crate) fn with_node_auth(mut self, url: &str, auth: Option<NodeAuth>) -> Result<Self> {
         let mut url = validate_url(Url::parse(url)?)?;
         if let Some(auth) = &auth {
@@ -193,6 +254,16 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_sync_concurrency(mut self, sync_concurrency: usize) -> Self {
+        self.sync_concurrency = sync_concurrency;
+        self
+    }
+
+    pub(crate) fn with_max_milestone_lag(mut self, max_milestone_lag: u32) -> Self {
+        self.max_milestone_lag = max_milestone_lag;
+        self
+    }
+
     pub(crate) fn with_quorum(mut self, quorum: bool) -> Self {
         self.quorum = quorum;
         self
@@ -213,7 +284,33 @@ impl NodeManagerBuilder {
         self
     }
 
-    pub(crate) fn build(self, healthy_nodes: Arc<RwLock<HashMap<Node, InfoResponse>>>) -> NodeManager {
+    pub(crate) fn with_http_retry(mut self, policy: RetryPolicy) -> Self {
+        self.http_retry = policy;
+        self
+    }
+
+    pub(crate) fn with_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host.replace(pool_max_idle_per_host);
+        self
+    }
+
+    pub(crate) fn with_pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout.replace(pool_idle_timeout);
+        self
+    }
+
+    // Creates the HTTP client, which is shared by all requests of the node manager and the node syncing.
+    pub(crate) fn http_client(&self) -> Result<HttpClient> {
+        HttpClient::new(self.user_agent.clone())
+            .with_retry_policy(self.http_retry.clone())
+            .with_connection_pool(self.pool_max_idle_per_host, self.pool_idle_timeout)
+    }
+
+    pub(crate) fn build(
+        self,
+        healthy_nodes: Arc<RwLock<HashMap<Node, InfoResponse>>>,
+        http_client: HttpClient,
+    ) -> NodeManager {
         NodeManager {
             primary_node: self.primary_node.map(|node| node.into()),
             primary_pow_node: self.primary_pow_node.map(|node| node.into()),
@@ -222,12 +319,14 @@ impl NodeManagerBuilder {
                 .permanodes
                 .map(|nodes| nodes.into_iter().map(|node| node.into()).collect()),
             ignore_node_health: self.ignore_node_health,
+            node_sync_disabled: self.node_sync_disabled,
             node_sync_interval: self.node_sync_interval,
             healthy_nodes,
             quorum: self.quorum,
             min_quorum_size: self.min_quorum_size,
             quorum_threshold: self.quorum_threshold,
-            http_client: HttpClient::new(self.user_agent),
+            http_client,
+            node_cursor: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
@@ -240,11 +339,17 @@ impl Default for NodeManagerBuilder {
             nodes: HashSet::new(),
             permanodes: None,
             ignore_node_health: false,
+            node_sync_disabled: false,
             node_sync_interval: NODE_SYNC_INTERVAL,
+            sync_concurrency: DEFAULT_SYNC_CONCURRENCY,
+            max_milestone_lag: DEFAULT_MAX_MILESTONE_LAG,
             quorum: false,
             min_quorum_size: DEFAULT_MIN_QUORUM_SIZE,
             quorum_threshold: DEFAULT_QUORUM_THRESHOLD,
             user_agent: DEFAULT_USER_AGENT.to_string(),
+            http_retry: RetryPolicy::default(),
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
         }
     }
 }
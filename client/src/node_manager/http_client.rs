@@ -11,10 +11,20 @@ use serde_json::Value;
 
 use crate::{
     error::{Error, Result},
-    node_manager::node::Node,
+    node_manager::{builder::RetryPolicy, latency::NodeLatencies, node::Node},
 };
 pub(crate) struct Response(reqwest::Response);
 
+#[cfg(not(target_family = "wasm"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_family = "wasm")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::TimeoutFuture::new(duration.as_millis().try_into().unwrap_or(u32::MAX)).await;
+}
+
 impl Response {
     pub(crate) fn status(&self) -> u16 {
         self.0.status().as_u16()
@@ -37,6 +47,8 @@ impl Response {
 pub(crate) struct HttpClient {
     client: reqwest::Client,
     user_agent: String,
+    retry_policy: RetryPolicy,
+    pub(crate) latencies: NodeLatencies,
 }
 
 impl HttpClient {
@@ -44,6 +56,89 @@ impl HttpClient {
         Self {
             client: reqwest::Client::new(),
             user_agent,
+            retry_policy: RetryPolicy::default(),
+            latencies: NodeLatencies::default(),
+        }
+    }
+
+    pub(crate) fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    // Recreates the underlying client with the given connection pool settings, `None` keeps the default of reqwest.
+    // The browser manages the connections for wasm, so the settings are ignored there.
+    #[cfg_attr(target_family = "wasm", allow(unused_variables, unused_mut))]
+    pub(crate) fn with_connection_pool(
+        mut self,
+        max_idle_per_host: Option<usize>,
+        idle_timeout: Option<Duration>,
+    ) -> Result<Self> {
+        let mut client_builder = reqwest::Client::builder();
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            if let Some(max_idle_per_host) = max_idle_per_host {
+                client_builder = client_builder.pool_max_idle_per_host(max_idle_per_host);
+            }
+            if let Some(idle_timeout) = idle_timeout {
+                client_builder = client_builder.pool_idle_timeout(idle_timeout);
+            }
+        }
+
+        self.client = client_builder.build()?;
+        Ok(self)
+    }
+
+    // Send the request, retrying it according to the retry policy. POST requests are only retried if the policy allows
+    // it, since they aren't idempotent.
+    async fn send(&self, mut request_builder: RequestBuilder, node: &Node, is_post: bool) -> Result<reqwest::Response> {
+        let max_retries = if is_post && !self.retry_policy.retry_post {
+            0
+        } else {
+            self.retry_policy.max_retries
+        };
+        let mut retries = 0;
+
+        loop {
+            // The request can't be reused after sending it, so keep a copy for a possible retry.
+            let retry_request_builder = if retries < max_retries {
+                request_builder.try_clone()
+            } else {
+                None
+            };
+            let result = self.send_once(request_builder, node).await;
+
+            match retry_request_builder {
+                Some(retry_request_builder) if Self::is_transient(&result) => {
+                    let delay = self.retry_policy.delay(retries);
+                    log::debug!("retrying request to {} in {:?}", node.url, delay);
+                    sleep(delay).await;
+                    request_builder = retry_request_builder;
+                    retries += 1;
+                }
+                _ => return Ok(result?),
+            }
+        }
+    }
+
+    // Send the request and record its round-trip time for the node.
+    async fn send_once(&self, request_builder: RequestBuilder, node: &Node) -> reqwest::Result<reqwest::Response> {
+        let start_time = instant::Instant::now();
+        let resp = request_builder.send().await?;
+        let elapsed = start_time.elapsed();
+        self.latencies.record(&node.url, elapsed);
+        log::debug!("{:?} ms for {} {}", elapsed.as_millis(), resp.status(), node.url);
+        Ok(resp)
+    }
+
+    fn is_transient(result: &reqwest::Result<reqwest::Response>) -> bool {
+        match result {
+            Ok(resp) => resp.status().is_server_error(),
+            #[cfg(not(target_family = "wasm"))]
+            Err(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            #[cfg(target_family = "wasm")]
+            Err(e) => e.is_timeout() || e.is_request(),
         }
     }
 
@@ -67,6 +162,9 @@ impl HttpClient {
             if let Some(jwt) = &node_auth.jwt {
                 request_builder = request_builder.bearer_auth(jwt);
             }
+            for (name, value) in &node_auth.headers {
+                request_builder = request_builder.header(name, value);
+            }
         }
         #[cfg(not(target_family = "wasm"))]
         {
@@ -78,14 +176,7 @@ impl HttpClient {
     pub(crate) async fn get(&self, node: Node, timeout: Duration) -> Result<Response> {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
-        let start_time = instant::Instant::now();
-        let resp = request_builder.send().await?;
-        log::debug!(
-            "GET: {:?} ms for {} {}",
-            start_time.elapsed().as_millis(),
-            resp.status(),
-            node.url
-        );
+        let resp = self.send(request_builder, &node, false).await?;
         Self::parse_response(resp, &node.url).await
     }
 
@@ -94,20 +185,24 @@ impl HttpClient {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("accept", "application/vnd.iota.serializer-v1");
-        let resp = request_builder.send().await?;
+        let resp = self.send(request_builder, &node, false).await?;
         Self::parse_response(resp, &node.url).await
     }
 
     pub(crate) async fn post_json(&self, node: Node, timeout: Duration, json: Value) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
-        Self::parse_response(request_builder.json(&json).send().await?, &node.url).await
+        Self::parse_response(self.send(request_builder.json(&json), &node, true).await?, &node.url).await
     }
 
     pub(crate) async fn post_bytes(&self, node: Node, timeout: Duration, body: &[u8]) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("Content-Type", "application/vnd.iota.serializer-v1");
-        Self::parse_response(request_builder.body(body.to_vec()).send().await?, &node.url).await
+        Self::parse_response(
+            self.send(request_builder.body(body.to_vec()), &node, true).await?,
+            &node.url,
+        )
+        .await
     }
 }
@@ -0,0 +1,120 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracking of the request latency per node
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use url::Url;
+
+use crate::{constants::NODE_LATENCY_SAMPLE_WINDOW, node_manager::node::Node};
+
+/// Round-trip times of the latest requests per node, the moving average over them is used to compare nodes.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeLatencies {
+    samples: Arc<RwLock<HashMap<Url, VecDeque<Duration>>>>,
+}
+
+impl NodeLatencies {
+    /// Record the round-trip time of a request to the given url.
+    pub(crate) fn record(&self, url: &Url, latency: Duration) {
+        if let Ok(mut samples) = self.samples.write() {
+            let node_samples = samples.entry(node_origin(url)).or_default();
+            node_samples.push_back(latency);
+            if node_samples.len() > NODE_LATENCY_SAMPLE_WINDOW {
+                node_samples.pop_front();
+            }
+        }
+    }
+
+    /// Returns the moving average of the recorded latencies per node.
+    pub(crate) fn averages(&self) -> HashMap<Url, Duration> {
+        self.samples.read().map_or_else(
+            |_| HashMap::new(),
+            |samples| {
+                samples
+                    .iter()
+                    .filter(|(_, node_samples)| !node_samples.is_empty())
+                    .map(|(url, node_samples)| {
+                        (
+                            url.clone(),
+                            node_samples.iter().sum::<Duration>() / node_samples.len() as u32,
+                        )
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    /// Returns the node with the lowest average latency out of the provided nodes, nodes without recorded latencies
+    /// are ignored.
+    pub(crate) fn fastest<'a>(&self, nodes: &'a [Node]) -> Option<&'a Node> {
+        let averages = self.averages();
+
+        nodes
+            .iter()
+            .filter_map(|node| averages.get(&node_origin(&node.url)).map(|latency| (node, latency)))
+            .min_by_key(|(_, latency)| **latency)
+            .map(|(node, _)| node)
+    }
+}
+
+// Requests are sent to the node url with the path and query of the endpoint set, so only the origin identifies a node.
+fn node_origin(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_path("");
+    url.set_query(None);
+    url.set_fragment(None);
+    // Can only fail for urls that can't have credentials, in which case there are none to remove.
+    url.set_username("").ok();
+    url.set_password(None).ok();
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_average_and_fastest_node() {
+        let latencies = NodeLatencies::default();
+        let node_a = Node::from(Url::parse("http://node-a:14265").unwrap());
+        let node_b = Node::from(Url::parse("http://node-b:14265").unwrap());
+        let node_c = Node::from(Url::parse("http://node-c:14265").unwrap());
+
+        // Endpoint paths and queries don't matter for the node the latency is recorded for.
+        latencies.record(
+            &Url::parse("http://node-a:14265/api/core/v2/info").unwrap(),
+            Duration::from_millis(100),
+        );
+        latencies.record(
+            &Url::parse("http://node-a:14265/api/core/v2/tips").unwrap(),
+            Duration::from_millis(300),
+        );
+        latencies.record(
+            &Url::parse("http://node-b:14265/api/indexer/v1/outputs/basic?address=rms1").unwrap(),
+            Duration::from_millis(150),
+        );
+
+        let averages = latencies.averages();
+        assert_eq!(averages.len(), 2);
+        assert_eq!(averages[&node_a.url], Duration::from_millis(200));
+        assert_eq!(averages[&node_b.url], Duration::from_millis(150));
+
+        let nodes = [node_a.clone(), node_b.clone(), node_c.clone()];
+        assert_eq!(latencies.fastest(&nodes), Some(&node_b));
+        // Nodes without recorded latencies aren't picked.
+        assert_eq!(latencies.fastest(&[node_c]), None);
+
+        // Only the latest samples are taken into account.
+        for _ in 0..NODE_LATENCY_SAMPLE_WINDOW {
+            latencies.record(&node_a.url, Duration::from_millis(50));
+        }
+        assert_eq!(latencies.averages()[&node_a.url], Duration::from_millis(50));
+        assert_eq!(latencies.fastest(&nodes), Some(&node_a));
+    }
+}
@@ -5,21 +5,24 @@
 
 pub mod builder;
 pub(crate) mod http_client;
+pub(crate) mod latency;
 /// Structs for nodes
 pub mod node;
 pub(crate) mod syncing;
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    sync::{atomic::AtomicUsize, Arc, RwLock},
     time::Duration,
 };
 
 use iota_types::api::core::response::InfoResponse;
 use serde_json::Value;
+use url::Url;
 
 use self::{http_client::HttpClient, node::Node};
 use crate::{
+    client::redacted_url,
     error::{Error, Result},
     node_manager::builder::NodeManagerBuilder,
 };
@@ -29,16 +32,19 @@ use crate::{
 #[derive(Clone)]
 pub(crate) struct NodeManager {
     pub(crate) primary_node: Option<Node>,
-    primary_pow_node: Option<Node>,
+    pub(crate) primary_pow_node: Option<Node>,
     pub(crate) nodes: HashSet<Node>,
-    permanodes: Option<HashSet<Node>>,
+    pub(crate) permanodes: Option<HashSet<Node>>,
     pub(crate) ignore_node_health: bool,
-    node_sync_interval: Duration,
+    pub(crate) node_sync_disabled: bool,
+    pub(crate) node_sync_interval: Duration,
     pub(crate) healthy_nodes: Arc<RwLock<HashMap<Node, InfoResponse>>>,
-    quorum: bool,
-    min_quorum_size: usize,
-    quorum_threshold: usize,
+    pub(crate) quorum: bool,
+    pub(crate) min_quorum_size: usize,
+    pub(crate) quorum_threshold: usize,
     pub(crate) http_client: HttpClient,
+    // Position in the node pool for round-robin node selection, shared between clones.
+    pub(crate) node_cursor: Arc<AtomicUsize>,
 }
 
 impl std::fmt::Debug for NodeManager {
@@ -49,6 +55,7 @@ impl std::fmt::Debug for NodeManager {
         d.field("nodes", &self.nodes);
         d.field("permanodes", &self.permanodes);
         d.field("ignore_node_health", &self.ignore_node_health);
+        d.field("node_sync_disabled", &self.node_sync_disabled);
         d.field("node_sync_interval", &self.node_sync_interval);
         d.field("healthy_nodes", &self.healthy_nodes);
         d.field("quorum", &self.quorum);
@@ -62,6 +69,30 @@ impl NodeManager {
         NodeManagerBuilder::new()
     }
 
+    // The healthy nodes, or all nodes if the node health is ignored or the nodes aren't synced, without disabled ones
+    // and sorted by url.
+    pub(crate) fn node_pool(&self) -> Result<Vec<Node>> {
+        #[cfg(not(target_family = "wasm"))]
+        let mut pool: Vec<Node> = if self.ignore_node_health || self.node_sync_disabled {
+            self.nodes.iter().cloned().collect()
+        } else {
+            self.healthy_nodes
+                .read()
+                .map_err(|_| crate::Error::PoisonError)?
+                .keys()
+                .cloned()
+                .collect()
+        };
+        #[cfg(target_family = "wasm")]
+        let mut pool: Vec<Node> = self.nodes.iter().cloned().collect();
+
+        pool.retain(|node| !node.disabled);
+        // Sort the pool, so the order doesn't depend on the iteration order of the underlying set.
+        pool.sort_by(|a, b| a.url.as_str().cmp(b.url.as_str()));
+
+        Ok(pool)
+    }
+
     fn get_nodes(
         &self,
         path: &str,
@@ -96,7 +127,7 @@ impl NodeManager {
         }
 
         // Add other nodes in random order, so they are not always used in the same order
-        let nodes_random_order = if !self.ignore_node_health {
+        let nodes_random_order = if !self.ignore_node_health && !self.node_sync_disabled {
             #[cfg(not(target_family = "wasm"))]
             {
                 self.healthy_nodes
@@ -225,17 +256,6 @@ impl NodeManager {
                     Ok(res) => {
                         match res.status() {
                             200 => {
-                                // Handle node_info extra because we also want to return the url
-                                if path == "api/core/v2/info" {
-                                    let node_info: InfoResponse = res.into_json().await?;
-                                    let wrapper = crate::node_api::core::routes::NodeInfoWrapper {
-                                        node_info,
-                                        url: format!("{}://{}", node.url.scheme(), node.url.host_str().unwrap_or("")),
-                                    };
-                                    let serde_res = serde_json::to_string(&wrapper)?;
-                                    return Ok(serde_json::from_str(&serde_res)?);
-                                }
-
                                 match res.into_json::<T>().await {
                                     Ok(result_data) => {
                                         let counters = result.entry(serde_json::to_string(&result_data)?).or_insert(0);
@@ -297,6 +317,39 @@ impl NodeManager {
         }
     }
 
+    // Requests the nodes one after another until one of them returns a result, which is returned together with the
+    // url of that node, without path and credentials.
+    pub(crate) async fn get_request_with_url<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Duration,
+    ) -> Result<(T, Url)> {
+        let mut error = None;
+        for node in self.get_nodes(path, None, false, false)? {
+            match self.http_client.get(node.clone(), timeout).await {
+                Ok(res) => match res.into_json::<T>().await {
+                    Ok(result_data) => {
+                        let mut url = redacted_url(&node);
+                        url.set_path("");
+                        url.set_query(None);
+                        return Ok((result_data, url));
+                    }
+                    Err(e) => {
+                        error.replace(e);
+                    }
+                },
+                Err(Error::ResponseError { code: 404, url, .. }) => {
+                    error.replace(crate::Error::NotFound(url));
+                }
+                Err(err) => {
+                    error.replace(err);
+                }
+            }
+        }
+
+        Err(error.unwrap_or_else(|| Error::Node("couldn't get a result from any node".into())))
+    }
+
     // Only used for api/core/v2/blocks/{blockID}, that's why we don't need the quorum stuff
     pub(crate) async fn get_request_bytes(
         &self,
@@ -343,7 +396,11 @@ impl NodeManager {
         local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
-        let nodes = self.get_nodes(path, None, !local_pow, false)?;
+        let nodes = match self.get_nodes(path, None, !local_pow, false) {
+            // No healthy node has the PoW feature enabled, the caller can fall back to local PoW
+            Err(Error::HealthyNodePoolEmpty) if !local_pow => Vec::new(),
+            nodes => nodes?,
+        };
         if nodes.is_empty() {
             return Err(Error::Node("no available nodes with remote Pow".into()));
         }
@@ -380,7 +437,11 @@ impl NodeManager {
         local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
-        let nodes = self.get_nodes(path, None, !local_pow, false)?;
+        let nodes = match self.get_nodes(path, None, !local_pow, false) {
+            // No healthy node has the PoW feature enabled, the caller can fall back to local PoW
+            Err(Error::HealthyNodePoolEmpty) if !local_pow => Vec::new(),
+            nodes => nodes?,
+        };
         if nodes.is_empty() {
             return Err(Error::Node("no available nodes with remote Pow".into()));
         }
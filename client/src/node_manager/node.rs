@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::hash::Hash;
+use std::{collections::BTreeMap, hash::Hash};
 
 use serde::{Deserialize, Serialize};
 pub use url::Url;
@@ -14,6 +14,9 @@ pub struct NodeAuth {
     pub jwt: Option<String>,
     /// Username and password.
     pub basic_auth_name_pwd: Option<(String, String)>,
+    /// Additional headers sent with every request, e.g. an API key required by a proxy in front of the node.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 /// Node definition.
@@ -1,9 +1,14 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::atomic::Ordering;
+
+use url::Url;
 #[cfg(not(target_family = "wasm"))]
 use {
+    super::http_client::HttpClient,
     crate::NetworkInfo,
+    futures::StreamExt,
     iota_types::{api::core::response::InfoResponse, block::protocol::ProtocolParameters},
     std::collections::HashMap,
     std::{
@@ -19,14 +24,42 @@ use crate::{Client, Error, Result};
 
 impl Client {
     /// Get a node candidate from the healthy node pool.
+    /// The primary node is always preferred, otherwise consecutive calls rotate through the pool in a round-robin
+    /// fashion, so the load is spread across all healthy nodes.
     pub fn get_node(&self) -> Result<Node> {
         if let Some(primary_node) = &self.node_manager.primary_node {
             return Ok(primary_node.clone());
         }
 
-        let pool = self.node_manager.nodes.clone();
+        let mut pool = self.node_manager.node_pool()?;
+
+        if pool.is_empty() {
+            return Err(Error::HealthyNodePoolEmpty);
+        }
 
-        pool.into_iter().next().ok_or(Error::HealthyNodePoolEmpty)
+        let index = self.node_manager.node_cursor.fetch_add(1, Ordering::Relaxed) % pool.len();
+
+        Ok(pool.swap_remove(index))
+    }
+
+    /// Returns the moving average of the latencies of the latest requests per node.
+    pub fn node_latencies(&self) -> std::collections::HashMap<Url, std::time::Duration> {
+        self.node_manager.http_client.latencies.averages()
+    }
+
+    /// Get the node with the lowest average request latency out of the primary node and the healthy node pool.
+    /// Falls back to [`Client::get_node()`] if no latencies have been recorded for these nodes yet.
+    pub fn get_fastest_node(&self) -> Result<Node> {
+        let mut candidates = self.node_manager.node_pool()?;
+        if let Some(primary_node) = &self.node_manager.primary_node {
+            candidates.push(primary_node.clone());
+        }
+
+        self.node_manager
+            .http_client
+            .latencies
+            .fastest(&candidates)
+            .map_or_else(|| self.get_node(), |node| Ok(node.clone()))
     }
 
     /// returns the unhealthy nodes.
@@ -46,20 +79,34 @@ impl Client {
 
     /// Sync the node lists per node_sync_interval milliseconds
     #[cfg(not(target_family = "wasm"))]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn start_sync_process(
         runtime: &Runtime,
+        http_client: HttpClient,
         sync: Arc<RwLock<HashMap<Node, InfoResponse>>>,
         nodes: HashSet<Node>,
         node_sync_interval: Duration,
         network_info: Arc<RwLock<NetworkInfo>>,
         ignore_node_health: bool,
+        sync_concurrency: usize,
+        max_milestone_lag: u32,
     ) -> tokio::task::JoinHandle<()> {
         runtime.spawn(async move {
             loop {
                 // Delay first since the first `sync_nodes` call is made by the builder to ensure the node list is
                 // filled before the client is used.
                 sleep(node_sync_interval).await;
-                if let Err(e) = Self::sync_nodes(&sync, &nodes, &network_info, ignore_node_health).await {
+                if let Err(e) = Self::sync_nodes(
+                    &http_client,
+                    &sync,
+                    &nodes,
+                    &network_info,
+                    ignore_node_health,
+                    sync_concurrency,
+                    max_milestone_lag,
+                )
+                .await
+                {
                     log::warn!("Syncing nodes failed: {e}");
                 }
             }
@@ -68,25 +115,39 @@ impl Client {
 
     #[cfg(not(target_family = "wasm"))]
     pub(crate) async fn sync_nodes(
+        http_client: &HttpClient,
         sync: &Arc<RwLock<HashMap<Node, InfoResponse>>>,
         nodes: &HashSet<Node>,
         network_info: &Arc<RwLock<NetworkInfo>>,
         ignore_node_health: bool,
+        sync_concurrency: usize,
+        max_milestone_lag: u32,
     ) -> Result<()> {
         log::debug!("sync_nodes");
         let mut healthy_nodes = HashMap::new();
         let mut network_nodes: HashMap<String, Vec<(InfoResponse, Node)>> = HashMap::new();
 
-        for node in nodes {
+        // Request the node infos with a limited amount of requests at once.
+        let node_infos = futures::stream::iter(nodes.iter().cloned())
+            .map(|node| async move {
+                let info =
+                    Self::get_node_info_with_http_client(http_client, node.url.as_ref(), node.auth.clone()).await;
+                (node, info)
+            })
+            .buffered(sync_concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        for (node, info) in node_infos {
             // Put the healthy node url into the network_nodes
-            if let Ok(info) = Self::get_node_info(node.url.as_ref(), node.auth.clone()).await {
+            if let Ok(info) = info {
                 if info.status.is_healthy || ignore_node_health {
                     match network_nodes.get_mut(&info.protocol.network_name) {
                         Some(network_node_entry) => {
-                            network_node_entry.push((info, node.clone()));
+                            network_node_entry.push((info, node));
                         }
                         None => {
-                            network_nodes.insert(info.protocol.network_name.clone(), vec![(info, node.clone())]);
+                            network_nodes.insert(info.protocol.network_name.clone(), vec![(info, node)]);
                         }
                     }
                 } else {
@@ -106,8 +167,29 @@ impl Client {
             }
         }
 
-        if let Some(nodes) = network_nodes.get(most_nodes.0) {
-            if let Some((info, _node_url)) = nodes.first() {
+        let network_name = most_nodes.0.to_string();
+        if let Some(nodes) = network_nodes.get_mut(&network_name) {
+            // Drop the nodes that are too far behind the most recent node of the network.
+            let latest_milestone_index = nodes
+                .iter()
+                .map(|(info, _)| info.status.latest_milestone.index)
+                .max()
+                .unwrap_or_default();
+            if !ignore_node_health {
+                nodes.retain(|(info, node)| {
+                    let lag = latest_milestone_index - info.status.latest_milestone.index;
+                    if lag > max_milestone_lag {
+                        log::debug!("{} is {lag} milestones behind", node.url);
+                    }
+                    lag <= max_milestone_lag
+                });
+            }
+
+            // Take the network info from the most recent node.
+            if let Some((info, _node_url)) = nodes
+                .iter()
+                .find(|(info, _)| info.status.latest_milestone.index == latest_milestone_index)
+            {
                 let mut network_info = network_info.write().map_err(|_| crate::Error::PoisonError)?;
 
                 network_info.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
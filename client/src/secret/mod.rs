@@ -64,6 +64,24 @@ pub trait SecretManage: Send + Sync {
         options: Option<GenerateAddressOptions>,
     ) -> crate::Result<Vec<Address>>;
 
+    /// Generates addresses like [SecretManage::generate_addresses()], for large ranges of address indexes like the ones
+    /// of address gap scans.
+    ///
+    /// The returned addresses are identical to the ones of [SecretManage::generate_addresses()], which the default
+    /// implementation calls. Secret managers for which deriving the key shared by all addresses of a range is expensive
+    /// override it to derive that key only once.
+    async fn generate_addresses_batched(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        internal: bool,
+        options: Option<GenerateAddressOptions>,
+    ) -> crate::Result<Vec<Address>> {
+        self.generate_addresses(coin_type, account_index, address_indexes, internal, options)
+            .await
+    }
+
     /// Sign on `essence`, unlock `input` by returning an [Unlock].
     async fn signature_unlock(
         &self,
@@ -93,6 +111,35 @@ pub trait SecretManageExt {
         prepared_transaction_data: &PreparedTransactionData,
         time: Option<u32>,
     ) -> crate::Result<Unlocks>;
+
+    /// Signs transaction essence like [SecretManageExt::sign_transaction_essence()], but returns every unlock together
+    /// with the address it unlocks, which helps to find out which input was unlocked by which key.
+    async fn sign_transaction_essence_verbose(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        time: Option<u32>,
+    ) -> crate::Result<Vec<(Unlock, Address)>>
+    where
+        Self: Sync,
+    {
+        let unlocks = self.sign_transaction_essence(prepared_transaction_data, time).await?;
+        let TransactionEssence::Regular(regular) = &prepared_transaction_data.essence;
+
+        unlocks
+            .iter()
+            .zip(&prepared_transaction_data.inputs_data)
+            .map(|(unlock, input)| {
+                let alias_transition = is_alias_transition(input, regular.outputs()).map(|t| t.0);
+                let (input_address, _) = input.output.required_and_unlocked_address(
+                    time.unwrap_or_else(unix_timestamp_now),
+                    input.output_metadata.output_id(),
+                    alias_transition,
+                )?;
+
+                Ok((unlock.clone(), input_address))
+            })
+            .collect()
+    }
 }
 
 /// Supported secret managers
@@ -258,6 +305,40 @@ impl SecretManage for SecretManager {
         }
     }
 
+    async fn generate_addresses_batched(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        internal: bool,
+        options: Option<GenerateAddressOptions>,
+    ) -> crate::Result<Vec<Address>> {
+        match self {
+            #[cfg(feature = "stronghold")]
+            Self::Stronghold(secret_manager) => {
+                secret_manager
+                    .generate_addresses_batched(coin_type, account_index, address_indexes, internal, options)
+                    .await
+            }
+            #[cfg(feature = "ledger_nano")]
+            Self::LedgerNano(secret_manager) => {
+                secret_manager
+                    .generate_addresses_batched(coin_type, account_index, address_indexes, internal, options)
+                    .await
+            }
+            Self::Mnemonic(secret_manager) => {
+                secret_manager
+                    .generate_addresses_batched(coin_type, account_index, address_indexes, internal, options)
+                    .await
+            }
+            Self::Placeholder(secret_manager) => {
+                secret_manager
+                    .generate_addresses_batched(coin_type, account_index, address_indexes, internal, options)
+                    .await
+            }
+        }
+    }
+
     async fn signature_unlock(
         &self,
         input: &InputSigningData,
@@ -21,7 +21,10 @@ pub(super) const SEED_RECORD_PATH: &[u8] = b"iota-wallet-seed";
 /// The value has been hard-coded historically.
 pub(super) const DERIVE_OUTPUT_RECORD_PATH: &[u8] = b"iota-wallet-derived";
 
-/// The client path for the seed.
+/// Stronghold record path to a derived SLIP-10 private key that is the parent of the keys of a range of addresses.
+pub(super) const DERIVE_PARENT_RECORD_PATH: &[u8] = b"iota-wallet-derived-parent";
+
+/// The default client path for the seed.
 ///
 /// The value has been hard-coded historically.
 pub(super) const PRIVATE_DATA_CLIENT_PATH: &[u8] = b"iota_seed";
@@ -105,27 +105,43 @@ pub struct StrongholdAdapter {
     /// The path to a Stronghold snapshot file.
     #[builder(setter(skip))]
     pub snapshot_path: PathBuf,
+
+    /// The Stronghold client path used for all vault and store operations.
+    ///
+    /// Defaults to the historically hard-coded client path. Using different client paths allows to keep independent
+    /// secrets (e.g. the mnemonics of multiple tenants) in their own Stronghold client within the same snapshot. See
+    /// also [`set_client_path()`].
+    ///
+    /// [`set_client_path()`]: self::StrongholdAdapter::set_client_path()
+    #[builder(setter(into))]
+    client_path: Vec<u8>,
+
+    /// Whether to keep a timestamped copy of the previous snapshot file every time a snapshot is written.
+    ///
+    /// Writing a snapshot is atomic on its own, as `stronghold_engine` writes it to a temporary file that is then
+    /// renamed over the previous one. The backup additionally allows going back to the previous state.
+    backup_snapshot: bool,
 }
 
 fn check_or_create_snapshot(
     stronghold: &Stronghold,
     key_provider: &KeyProvider,
     snapshot_path: &SnapshotPath,
+    client_path: &[u8],
 ) -> Result<()> {
-    let result = stronghold.load_client_from_snapshot(PRIVATE_DATA_CLIENT_PATH, key_provider, snapshot_path);
+    let result = stronghold.load_client_from_snapshot(client_path, key_provider, snapshot_path);
 
     match result {
         Err(iota_stronghold::ClientError::SnapshotFileMissing(_)) => {
-            stronghold.create_client(PRIVATE_DATA_CLIENT_PATH)?;
+            stronghold.create_client(client_path)?;
             stronghold.commit_with_keyprovider(snapshot_path, key_provider)?;
         }
         Err(iota_stronghold::ClientError::ClientAlreadyLoaded(_)) => {
-            stronghold.get_client(PRIVATE_DATA_CLIENT_PATH)?;
+            stronghold.get_client(client_path)?;
         }
-        Err(iota_stronghold::ClientError::Inner(ref err_msg)) => {
-            // Matching the error string is not ideal but stronghold doesn't wrap the error types at the moment.
-            if err_msg.to_string().contains("XCHACHA20-POLY1305") {
-                return Err(Error::StrongholdInvalidPassword);
+        Err(ref err) => {
+            if let Some(err) = snapshot_error(err) {
+                return Err(err);
             }
         }
         _ => {}
@@ -134,6 +150,39 @@ fn check_or_create_snapshot(
     Ok(())
 }
 
+/// Tells apart a wrong password, a corrupted snapshot and an unsupported snapshot version from an error returned while
+/// loading a snapshot.
+fn snapshot_error(err: &iota_stronghold::ClientError) -> Option<Error> {
+    if let iota_stronghold::ClientError::Inner(err_msg) = err {
+        // Matching the error string is not ideal but stronghold doesn't wrap the error types at the moment.
+        // Note that a snapshot truncated within its ciphertext fails the authentication tag check just like a wrong
+        // password does, so these two cases can't be distinguished.
+        if err_msg.contains("XCHACHA20-POLY1305") {
+            return Some(Error::StrongholdInvalidPassword);
+        }
+        if err_msg.contains("Unsupported version") {
+            return Some(Error::StrongholdVersionMismatch);
+        }
+        if err_msg.contains("Not a Snapshot") || err_msg.contains("Decompression failed") {
+            return Some(Error::StrongholdSnapshotCorrupted);
+        }
+    }
+
+    None
+}
+
+/// The path of a timestamped backup of the snapshot at `snapshot_path`.
+fn backup_snapshot_path(snapshot_path: &Path) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis();
+    let mut backup_path = snapshot_path.as_os_str().to_os_string();
+    backup_path.push(format!(".{timestamp}.bak"));
+
+    backup_path.into()
+}
+
 /// Extra / custom builder method implementations.
 impl StrongholdAdapterBuilder {
     /// Use an user-input password string to derive a key to use Stronghold.
@@ -161,9 +210,15 @@ impl StrongholdAdapterBuilder {
     pub fn build<P: AsRef<Path>>(mut self, snapshot_path: P) -> Result<StrongholdAdapter> {
         // In any case, Stronghold - as a necessary component - needs to be present at this point.
         let stronghold = self.stronghold.unwrap_or_default();
+        let client_path = self.client_path.unwrap_or_else(|| PRIVATE_DATA_CLIENT_PATH.to_vec());
 
         if let Some(key_provider) = &self.key_provider {
-            check_or_create_snapshot(&stronghold, key_provider, &SnapshotPath::from_path(&snapshot_path))?;
+            check_or_create_snapshot(
+                &stronghold,
+                key_provider,
+                &SnapshotPath::from_path(&snapshot_path),
+                &client_path,
+            )?;
         }
 
         let has_key_provider = self.key_provider.is_some();
@@ -203,6 +258,8 @@ impl StrongholdAdapterBuilder {
             timeout: self.timeout.unwrap_or(None),
             timeout_task: self.timeout_task.unwrap_or_else(|| Arc::new(Mutex::new(None))),
             snapshot_path: snapshot_path.as_ref().to_path_buf(),
+            client_path,
+            backup_snapshot: self.backup_snapshot.unwrap_or_default(),
         })
     }
 }
@@ -238,7 +295,7 @@ impl StrongholdAdapter {
         let snapshot_path = SnapshotPath::from_path(&self.snapshot_path);
         let stronghold = self.stronghold.lock().await;
 
-        check_or_create_snapshot(&stronghold, &key_provider, &snapshot_path)?;
+        check_or_create_snapshot(&stronghold, &key_provider, &snapshot_path, &self.client_path)?;
 
         *key_provider_guard = Some(key_provider);
         drop(key_provider_guard);
@@ -294,7 +351,7 @@ impl StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .store()
             .keys()?;
 
@@ -442,6 +499,30 @@ impl StrongholdAdapter {
         }
     }
 
+    /// Get the Stronghold client path used for vault and store operations.
+    pub fn get_client_path(&self) -> &[u8] {
+        &self.client_path
+    }
+
+    /// Switch the Stronghold client path used for subsequent vault and store operations.
+    ///
+    /// If the key is available, the client is loaded from the snapshot, or created if it doesn't exist yet.
+    /// Otherwise, this happens when the password is set again.
+    pub async fn set_client_path(&mut self, client_path: Vec<u8>) -> Result<()> {
+        if let Some(key_provider) = &*self.key_provider.lock().await {
+            check_or_create_snapshot(
+                &*self.stronghold.lock().await,
+                key_provider,
+                &SnapshotPath::from_path(&self.snapshot_path),
+                &client_path,
+            )?;
+        }
+
+        self.client_path = client_path;
+
+        Ok(())
+    }
+
     /// Restart the key clearing task.
     ///
     /// This is equivalent to calling `set_timeout()` with the currently set `timeout`.
@@ -460,11 +541,15 @@ impl StrongholdAdapter {
             return Err(Error::StrongholdKeyCleared);
         };
 
-        self.stronghold.lock().await.load_client_from_snapshot(
-            PRIVATE_DATA_CLIENT_PATH,
-            key_provider,
-            &SnapshotPath::from_path(&self.snapshot_path),
-        )?;
+        self.stronghold
+            .lock()
+            .await
+            .load_client_from_snapshot(
+                &self.client_path,
+                key_provider,
+                &SnapshotPath::from_path(&self.snapshot_path),
+            )
+            .map_err(|err| snapshot_error(&err).unwrap_or_else(|| err.into()))?;
 
         Ok(())
     }
@@ -474,6 +559,10 @@ impl StrongholdAdapter {
     ///
     /// It doesn't unload the snapshot; see also [`unload_stronghold_snapshot()`].
     ///
+    /// Writing the snapshot doesn't corrupt an existing one if it's interrupted, as `stronghold_engine` writes it to a
+    /// temporary file first and then renames it. If `backup_snapshot` is set, the existing snapshot is also copied to
+    /// `<snapshot_path>.<unix timestamp in milliseconds>.bak` beforehand.
+    ///
     /// [`unload_stronghold_snapshot()`]: Self::unload_stronghold_snapshot()
     #[allow(clippy::significant_drop_tightening)]
     pub async fn write_stronghold_snapshot(&mut self, snapshot_path: Option<&Path>) -> Result<()> {
@@ -485,10 +574,17 @@ impl StrongholdAdapter {
             return Err(Error::StrongholdKeyCleared);
         };
 
-        self.stronghold.lock().await.commit_with_keyprovider(
-            &SnapshotPath::from_path(snapshot_path.unwrap_or(&self.snapshot_path)),
-            key_provider,
-        )?;
+        let snapshot_path = snapshot_path.unwrap_or(&self.snapshot_path);
+
+        if self.backup_snapshot && snapshot_path.exists() {
+            std::fs::copy(snapshot_path, backup_snapshot_path(snapshot_path))
+                .map_err(Error::StrongholdSnapshotBackup)?;
+        }
+
+        self.stronghold
+            .lock()
+            .await
+            .commit_with_keyprovider(&SnapshotPath::from_path(snapshot_path), key_provider)?;
 
         Ok(())
     }
@@ -601,4 +697,79 @@ mod tests {
 
         fs::remove_file(stronghold_path).unwrap();
     }
+
+    #[tokio::test]
+    async fn stronghold_snapshot_corrupted() {
+        let stronghold_path = "stronghold_snapshot_corrupted.stronghold";
+        StrongholdAdapter::builder()
+            .password("drowssap")
+            .build(stronghold_path)
+            .unwrap();
+
+        let snapshot = fs::read(stronghold_path).unwrap();
+
+        // Wrong password
+        assert!(matches!(
+            StrongholdAdapter::builder().password("password").build(stronghold_path),
+            Err(Error::StrongholdInvalidPassword)
+        ));
+
+        // Unsupported version, stored right after the 5 magic bytes
+        let mut future_version = snapshot.clone();
+        future_version[5] = 0xff;
+        fs::write(stronghold_path, future_version).unwrap();
+        assert!(matches!(
+            StrongholdAdapter::builder().password("drowssap").build(stronghold_path),
+            Err(Error::StrongholdVersionMismatch)
+        ));
+
+        // Truncated snapshot
+        fs::write(stronghold_path, &snapshot[..snapshot.len().min(32)]).unwrap();
+        assert!(matches!(
+            StrongholdAdapter::builder().password("drowssap").build(stronghold_path),
+            Err(Error::StrongholdSnapshotCorrupted)
+        ));
+
+        fs::remove_file(stronghold_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stronghold_snapshot_backup() {
+        let stronghold_dir = "stronghold_snapshot_backup";
+        fs::remove_dir_all(stronghold_dir).unwrap_or(());
+        fs::create_dir_all(stronghold_dir).unwrap();
+        let stronghold_path = format!("{stronghold_dir}/test.stronghold");
+
+        let mut adapter = StrongholdAdapter::builder()
+            .password("drowssap")
+            .backup_snapshot(true)
+            .build(&stronghold_path)
+            .unwrap();
+        adapter.insert(b"key", b"value").await.unwrap();
+        adapter.write_stronghold_snapshot(None).await.unwrap();
+
+        // Only the snapshot and a backup of the previous one, no leftover temporary file.
+        let mut files = fs::read_dir(stronghold_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], "test.stronghold");
+        assert!(files[1].starts_with("test.stronghold.") && files[1].ends_with(".bak"));
+
+        // The snapshot holds the new state and the backup the previous one.
+        let mut adapter = StrongholdAdapter::builder()
+            .password("drowssap")
+            .build(&stronghold_path)
+            .unwrap();
+        assert_eq!(adapter.get(b"key").await.unwrap(), Some(b"value".to_vec()));
+        let mut backup = StrongholdAdapter::builder()
+            .password("drowssap")
+            .build(format!("{stronghold_dir}/{}", files[1]))
+            .unwrap();
+        assert_eq!(backup.get(b"key").await.unwrap(), None);
+
+        fs::remove_dir_all(stronghold_dir).unwrap();
+    }
 }
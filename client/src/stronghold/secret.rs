@@ -19,7 +19,7 @@ use iota_types::block::{
 use zeroize::Zeroize;
 
 use super::{
-    common::{DERIVE_OUTPUT_RECORD_PATH, PRIVATE_DATA_CLIENT_PATH, SECRET_VAULT_PATH, SEED_RECORD_PATH},
+    common::{DERIVE_OUTPUT_RECORD_PATH, DERIVE_PARENT_RECORD_PATH, SECRET_VAULT_PATH, SEED_RECORD_PATH},
     StrongholdAdapter,
 };
 use crate::{
@@ -77,6 +77,79 @@ impl SecretManage for StrongholdAdapter {
         Ok(addresses)
     }
 
+    /// Generates addresses like [SecretManage::generate_addresses()], but derives the key of
+    /// `m/44'/coin_type'/account_index'/internal'` only once and then each address key from it with a single SLIP-10
+    /// step, instead of deriving all five steps from the seed for every address index.
+    ///
+    /// This cuts the SLIP-10 work per address by a factor of five. Do note that the time per address is dominated by
+    /// Stronghold's handling of the vault records and the Ed25519 public key computation, which are still needed for
+    /// every address index, so the overall speedup is small (a few percent for a range of 50 addresses).
+    #[allow(clippy::significant_drop_tightening)]
+    async fn generate_addresses_batched(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        internal: bool,
+        _options: Option<GenerateAddressOptions>,
+    ) -> Result<Vec<Address>> {
+        // See `generate_addresses()`.
+        if !self.is_key_available().await {
+            return Err(Error::StrongholdKeyCleared);
+        }
+
+        // Stronghold arguments.
+        let seed_location = Slip10DeriveInput::Seed(Location::generic(SECRET_VAULT_PATH, SEED_RECORD_PATH));
+        let parent_location = Location::generic(SECRET_VAULT_PATH, DERIVE_PARENT_RECORD_PATH);
+        let derive_location = Location::generic(SECRET_VAULT_PATH, DERIVE_OUTPUT_RECORD_PATH);
+
+        // The shared private key is stored in a fixed record, so Stronghold stays locked until it's deleted again,
+        // otherwise concurrent calls could overwrite or delete it in between.
+        let stronghold = self.stronghold.lock().await;
+        let client = stronghold.get_client(&self.client_path)?;
+
+        // Derive the SLIP-10 private key shared by all addresses in the vault.
+        let parent_chain = Chain::from_u32_hardened(vec![44u32, coin_type, account_index, internal as u32]);
+        client
+            .execute_procedure(procedures::Slip10Derive {
+                chain: parent_chain,
+                input: seed_location,
+                output: parent_location.clone(),
+            })
+            .map_err(slip10_derive_error)?;
+
+        let addresses = address_indexes
+            .map(|address_index| {
+                // Derive the SLIP-10 private key of the address from the shared one.
+                client
+                    .execute_procedure(procedures::Slip10Derive {
+                        chain: Chain::from_u32_hardened(vec![address_index]),
+                        input: Slip10DeriveInput::Key(parent_location.clone()),
+                        output: derive_location.clone(),
+                    })
+                    .map_err(slip10_derive_error)?;
+
+                // Get the Ed25519 public key from the derived SLIP-10 private key in the vault.
+                let public_key = client.execute_procedure(procedures::PublicKey {
+                    ty: KeyType::Ed25519,
+                    private_key: derive_location.clone(),
+                })?;
+
+                // Hash the public key to get the address.
+                let hash = Blake2b256::digest(public_key);
+
+                Ok(Address::Ed25519(Ed25519Address::new(hash.into())))
+            })
+            .collect::<Result<Vec<_>>>();
+
+        // Delete the shared private key, also if the derivation failed, so it isn't persisted with the snapshot.
+        client
+            .vault(SECRET_VAULT_PATH)
+            .delete_secret(DERIVE_PARENT_RECORD_PATH)?;
+
+        addresses
+    }
+
     async fn signature_unlock(
         &self,
         input: &InputSigningData,
@@ -130,6 +203,22 @@ impl SecretManage for StrongholdAdapter {
     }
 }
 
+/// Maps the error of [Procedure::SLIP10Derive], telling apart a missing seed.
+fn slip10_derive_error(err: procedures::ProcedureError) -> Error {
+    match err {
+        procedures::ProcedureError::Engine(ref e) => {
+            // Custom error for missing vault error: https://github.com/iotaledger/stronghold.rs/blob/7f0a2e0637394595e953f9071fa74b1d160f51ec/client/src/types/error.rs#L170
+            if e.to_string().contains("does not exist") {
+                // Actually the seed, derived from the mnemonic, is not stored.
+                Error::StrongholdMnemonicMissing
+            } else {
+                err.into()
+            }
+        }
+        _ => err.into(),
+    }
+}
+
 /// Private methods for the secret manager implementation.
 impl StrongholdAdapter {
     /// Execute [Procedure::BIP39Recover] in Stronghold to put a mnemonic into the Stronghold vault.
@@ -137,7 +226,7 @@ impl StrongholdAdapter {
         self.stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .execute_procedure(procedures::BIP39Recover {
                 mnemonic,
                 passphrase,
@@ -149,28 +238,12 @@ impl StrongholdAdapter {
 
     /// Execute [Procedure::SLIP10Derive] in Stronghold to derive a SLIP-10 private key in the Stronghold vault.
     async fn slip10_derive(&self, chain: Chain, input: Slip10DeriveInput, output: Location) -> Result<()> {
-        if let Err(err) = self
-            .stronghold
+        self.stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .execute_procedure(procedures::Slip10Derive { chain, input, output })
-        {
-            match err {
-                iota_stronghold::procedures::ProcedureError::Engine(ref e) => {
-                    // Custom error for missing vault error: https://github.com/iotaledger/stronghold.rs/blob/7f0a2e0637394595e953f9071fa74b1d160f51ec/client/src/types/error.rs#L170
-                    if e.to_string().contains("does not exist") {
-                        // Actually the seed, derived from the mnemonic, is not stored.
-                        return Err(Error::StrongholdMnemonicMissing);
-                    } else {
-                        return Err(err.into());
-                    }
-                }
-                _ => {
-                    return Err(err.into());
-                }
-            }
-        };
+            .map_err(slip10_derive_error)?;
 
         Ok(())
     }
@@ -182,7 +255,7 @@ impl StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .execute_procedure(procedures::PublicKey {
                 ty: KeyType::Ed25519,
                 private_key,
@@ -195,7 +268,7 @@ impl StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .execute_procedure(procedures::Ed25519Sign {
                 private_key,
                 msg: msg.to_vec(),
@@ -225,7 +298,7 @@ impl StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .record_exists(&output)?
         {
             return Err(crate::Error::StrongholdMnemonicAlreadyStored);
@@ -280,6 +353,64 @@ mod tests {
         std::fs::remove_file(stronghold_path).unwrap_or(());
     }
 
+    #[tokio::test]
+    async fn test_address_generation_batched() {
+        let stronghold_path = "test_address_generation_batched.stronghold";
+        // Remove potential old stronghold file
+        std::fs::remove_file(stronghold_path).unwrap_or(());
+        let mnemonic = String::from(
+            "giant dynamic museum toddler six deny defense ostrich bomb access mercy blood explain muscle shoot shallow glad autumn author calm heavy hawk abuse rally",
+        );
+        let mut stronghold_adapter = StrongholdAdapter::builder()
+            .password("drowssap")
+            .build(stronghold_path)
+            .unwrap();
+
+        stronghold_adapter.store_mnemonic(mnemonic).await.unwrap();
+
+        for internal in [false, true] {
+            let addresses = stronghold_adapter
+                .generate_addresses(IOTA_COIN_TYPE, 0, 0..50, internal, None)
+                .await
+                .unwrap();
+            let batched_addresses = stronghold_adapter
+                .generate_addresses_batched(IOTA_COIN_TYPE, 0, 0..50, internal, None)
+                .await
+                .unwrap();
+
+            assert_eq!(batched_addresses.len(), 50);
+            assert_eq!(batched_addresses, addresses);
+        }
+
+        // Concurrent calls don't interfere with each other's shared private key.
+        let (account_0, account_1) = tokio::join!(
+            stronghold_adapter.generate_addresses_batched(IOTA_COIN_TYPE, 0, 0..10, false, None),
+            stronghold_adapter.generate_addresses_batched(IOTA_COIN_TYPE, 1, 0..10, false, None),
+        );
+        for (account_index, batched_addresses) in [(0, account_0.unwrap()), (1, account_1.unwrap())] {
+            let addresses = stronghold_adapter
+                .generate_addresses(IOTA_COIN_TYPE, account_index, 0..10, false, None)
+                .await
+                .unwrap();
+            assert_eq!(batched_addresses, addresses);
+        }
+
+        // The shared private key isn't kept in the vault.
+        assert!(
+            !stronghold_adapter
+                .stronghold
+                .lock()
+                .await
+                .get_client(&stronghold_adapter.client_path)
+                .unwrap()
+                .record_exists(&Location::generic(SECRET_VAULT_PATH, DERIVE_PARENT_RECORD_PATH))
+                .unwrap()
+        );
+
+        // Remove garbage after test, but don't care about the result
+        std::fs::remove_file(stronghold_path).unwrap_or(());
+    }
+
     #[tokio::test]
     async fn test_key_cleared() {
         let stronghold_path = "test_key_cleared.stronghold";
@@ -324,4 +455,67 @@ mod tests {
         // Remove garbage after test, but don't care about the result
         std::fs::remove_file(stronghold_path).unwrap_or(());
     }
+
+    #[tokio::test]
+    async fn test_client_paths() {
+        let stronghold_path = "test_client_paths.stronghold";
+        // Remove potential old stronghold file
+        std::fs::remove_file(stronghold_path).unwrap_or(());
+        let mnemonic_0 = String::from(
+            "giant dynamic museum toddler six deny defense ostrich bomb access mercy blood explain muscle shoot shallow glad autumn author calm heavy hawk abuse rally",
+        );
+        let mnemonic_1 = String::from(
+            "acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast",
+        );
+        let mut stronghold_adapter = StrongholdAdapter::builder()
+            .password("drowssap")
+            .client_path(b"tenant_0".to_vec())
+            .build(stronghold_path)
+            .unwrap();
+
+        stronghold_adapter.store_mnemonic(mnemonic_0).await.unwrap();
+
+        stronghold_adapter.set_client_path(b"tenant_1".to_vec()).await.unwrap();
+        assert_eq!(stronghold_adapter.get_client_path(), b"tenant_1");
+        // Each client has its own vault, so storing another mnemonic doesn't conflict.
+        stronghold_adapter.store_mnemonic(mnemonic_1.clone()).await.unwrap();
+
+        let addresses_1 = stronghold_adapter
+            .generate_addresses(IOTA_COIN_TYPE, 0, 0..1, false, None)
+            .await
+            .unwrap();
+
+        stronghold_adapter.set_client_path(b"tenant_0".to_vec()).await.unwrap();
+        let addresses_0 = stronghold_adapter
+            .generate_addresses(IOTA_COIN_TYPE, 0, 0..1, false, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            addresses_0[0].to_bech32("atoi"),
+            "atoi1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluehe53e".to_string()
+        );
+        assert_ne!(addresses_0, addresses_1);
+
+        // Both clients are persisted in the same snapshot.
+        let mut stronghold_adapter = StrongholdAdapter::builder()
+            .password("drowssap")
+            .client_path(b"tenant_1".to_vec())
+            .build(stronghold_path)
+            .unwrap();
+        assert_eq!(
+            stronghold_adapter
+                .generate_addresses(IOTA_COIN_TYPE, 0, 0..1, false, None)
+                .await
+                .unwrap(),
+            addresses_1
+        );
+        assert!(matches!(
+            stronghold_adapter.store_mnemonic(mnemonic_1).await,
+            Err(Error::StrongholdMnemonicAlreadyStored)
+        ));
+
+        // Remove garbage after test, but don't care about the result
+        std::fs::remove_file(stronghold_path).unwrap_or(());
+    }
 }
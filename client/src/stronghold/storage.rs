@@ -8,7 +8,7 @@ use std::ops::Deref;
 use async_trait::async_trait;
 use crypto::ciphers::chacha;
 
-use super::{common::PRIVATE_DATA_CLIENT_PATH, StrongholdAdapter};
+use super::StrongholdAdapter;
 use crate::{storage::StorageProvider, Error, Result};
 
 #[async_trait]
@@ -19,7 +19,7 @@ impl StorageProvider for StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .store()
             .get(k)?
         {
@@ -57,7 +57,7 @@ impl StorageProvider for StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .store()
             .insert(k.to_vec(), encrypted_value, None)?)
     }
@@ -67,7 +67,7 @@ impl StorageProvider for StrongholdAdapter {
             .stronghold
             .lock()
             .await
-            .get_client(PRIVATE_DATA_CLIENT_PATH)?
+            .get_client(&self.client_path)?
             .store()
             .delete(k)?)
     }
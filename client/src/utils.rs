@@ -12,13 +12,17 @@ use crypto::{
 };
 use iota_types::block::{
     address::{Address, AliasAddress, Ed25519Address, NftAddress},
-    output::{AliasId, NftId},
+    output::{AliasId, NftId, Output},
     payload::TaggedDataPayload,
+    Block,
 };
 use zeroize::Zeroize;
 
 use super::Client;
-use crate::error::{Error, Result};
+use crate::{
+    api::{DecodedBlock, OutputUnlockability},
+    error::{Error, Result},
+};
 
 /// Transforms bech32 to hex
 pub fn bech32_to_hex(bech32: &str) -> Result<String> {
@@ -68,11 +72,21 @@ pub fn is_address_valid(address: &str) -> bool {
     Address::try_from_bech32(address).is_ok()
 }
 
-/// Generates a new mnemonic.
+/// Generates a new mnemonic with 24 words.
 pub fn generate_mnemonic() -> Result<String> {
+    generate_mnemonic_with_word_count(24)
+}
+
+/// Generates a new mnemonic with the given number of words, which has to be 12, 15, 18, 21 or 24.
+pub fn generate_mnemonic_with_word_count(word_count: usize) -> Result<String> {
+    // Every 3 words encode 32 bits of entropy and 1 checksum bit.
+    let entropy_length = match word_count {
+        12 | 15 | 18 | 21 | 24 => word_count / 3 * 4,
+        _ => return Err(Error::InvalidMnemonicLength(word_count)),
+    };
     let mut entropy = [0u8; 32];
-    utils::rand::fill(&mut entropy)?;
-    let mnemonic = wordlist::encode(&entropy, &crypto::keys::bip39::wordlist::ENGLISH)
+    utils::rand::fill(&mut entropy[..entropy_length])?;
+    let mnemonic = wordlist::encode(&entropy[..entropy_length], &crypto::keys::bip39::wordlist::ENGLISH)
         .map_err(|e| crate::Error::InvalidMnemonic(format!("{e:?}")))?;
     entropy.zeroize();
     Ok(mnemonic)
@@ -126,6 +140,16 @@ impl Client {
         }
     }
 
+    /// Transforms hex encoded addresses to bech32 encoded addresses, the HRP is only requested once if not provided
+    pub async fn hex_to_bech32_batch(&self, hexes: &[String], bech32_hrp: Option<&str>) -> crate::Result<Vec<String>> {
+        let bech32_hrp = match bech32_hrp {
+            Some(hrp) => hrp.to_string(),
+            None => self.get_bech32_hrp().await?,
+        };
+
+        hexes.iter().map(|hex| hex_to_bech32(hex, &bech32_hrp)).collect()
+    }
+
     /// Transforms an alias id to a bech32 encoded address
     pub async fn alias_id_to_bech32(&self, alias_id: AliasId, bech32_hrp: Option<&str>) -> crate::Result<String> {
         match bech32_hrp {
@@ -161,11 +185,65 @@ impl Client {
         is_address_valid(address)
     }
 
-    /// Generates a new mnemonic.
+    /// Returns a valid Address parsed from a String, checking that its HRP matches the one of the network.
+    pub async fn parse_bech32_address_checked(&self, address: &str) -> crate::Result<Address> {
+        let (found, address) = Address::try_from_bech32(address)?;
+        let expected = self.get_bech32_hrp().await?;
+        if found != expected {
+            return Err(Error::Bech32HrpMismatch { expected, found });
+        }
+        Ok(address)
+    }
+
+    /// Checks if an address can unlock an output at the given local time (in seconds), taking timelock, expiration
+    /// and storage deposit return unlock conditions into account.
+    pub fn can_unlock(output: &Output, address: &Address, local_time: u32) -> OutputUnlockability {
+        let (unlock_conditions, output_address) = match output {
+            Output::Basic(output) => (output.unlock_conditions(), output.address()),
+            Output::Nft(output) => (output.unlock_conditions(), output.address()),
+            Output::Alias(output) => {
+                return if output.state_controller_address() == address || output.governor_address() == address {
+                    OutputUnlockability::Unlockable
+                } else {
+                    OutputUnlockability::Locked
+                };
+            }
+            Output::Foundry(output) => {
+                return if Address::Alias(*output.alias_address()) == *address {
+                    OutputUnlockability::Unlockable
+                } else {
+                    OutputUnlockability::Locked
+                };
+            }
+            Output::Treasury(_) => return OutputUnlockability::Locked,
+        };
+
+        if unlock_conditions.is_time_locked(local_time)
+            || unlock_conditions.locked_address(output_address, local_time) != address
+        {
+            return OutputUnlockability::Locked;
+        }
+
+        // The storage deposit only needs to be returned as long as the output didn't expire.
+        match unlock_conditions.storage_deposit_return() {
+            Some(sdruc) if !unlock_conditions.is_expired(local_time) => OutputUnlockability::UnlockableWithReturn {
+                return_address: *sdruc.return_address(),
+                amount: sdruc.amount(),
+            },
+            _ => OutputUnlockability::Unlockable,
+        }
+    }
+
+    /// Generates a new mnemonic with 24 words.
     pub fn generate_mnemonic() -> Result<String> {
         generate_mnemonic()
     }
 
+    /// Generates a new mnemonic with the given number of words, which has to be 12, 15, 18, 21 or 24.
+    pub fn generate_mnemonic_with_word_count(word_count: usize) -> Result<String> {
+        generate_mnemonic_with_word_count(word_count)
+    }
+
     /// Returns a seed for a mnemonic.
     pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Seed> {
         mnemonic_to_seed(mnemonic)
@@ -176,6 +254,13 @@ impl Client {
         mnemonic_to_hex_seed(mnemonic)
     }
 
+    /// Decodes the bytes of a block into a [`DecodedBlock`], exposing the content of its payload.
+    pub async fn decode_block(&self, bytes: &[u8]) -> Result<DecodedBlock> {
+        let block = Block::unpack_strict(bytes, &self.get_protocol_parameters().await?)?;
+
+        Ok(DecodedBlock::from(&block))
+    }
+
     /// UTF-8 encodes the `tag` of a given TaggedDataPayload.
     pub fn tag_to_utf8(payload: &TaggedDataPayload) -> Result<String> {
         String::from_utf8(payload.tag().to_vec()).map_err(|_| Error::TaggedData("found invalid UTF-8".to_string()))
@@ -0,0 +1,296 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use iota_client::{
+    api::{BalanceScan, ScanCursor},
+    block::{
+        address::{Address, Ed25519Address},
+        output::OutputId,
+        payload::transaction::TransactionId,
+        BlockId,
+    },
+    secret::SecretManager,
+    Client, Error,
+};
+
+use self::common::mock_node::{
+    basic_output, blocks_mock_node_client_builder, mock_node_client_builder, output_response, spawn_mock_node,
+};
+
+#[tokio::test]
+async fn send_if_balance_at_least() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let address = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_bech32_hrp("rms")
+        .with_range(0..1)
+        .finish()
+        .await
+        .unwrap()
+        .remove(0);
+
+    let output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let output = basic_output(1_000_000, Address::try_from_bech32(&address).unwrap().1);
+    let response = output_response(&output_id, &output, false);
+    let address_requests = Arc::new(AtomicUsize::new(0));
+    let address_requests_ = address_requests.clone();
+    let posted_blocks = Arc::new(AtomicUsize::new(0));
+    let posted_blocks_ = posted_blocks.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if path.starts_with(&format!("/api/indexer/v1/outputs/basic?address={address}&")) {
+            address_requests_.fetch_add(1, Ordering::SeqCst);
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [output_id] }).to_string(),
+            )
+        } else if path.starts_with("/api/indexer/v1/outputs/basic?") {
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        } else if path == format!("/api/core/v2/outputs/{output_id}") {
+            (200, response.clone())
+        } else if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            posted_blocks_.fetch_add(1, Ordering::SeqCst);
+            (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    // The account only holds 1_000_000.
+    assert!(matches!(
+        client
+            .send_if_balance_at_least(&secret_manager, 0, 2_000_000, vec![output.clone()])
+            .await,
+        Err(Error::InsufficientBalanceForCondition {
+            found: 1_000_000,
+            required: 2_000_000
+        })
+    ));
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 0);
+
+    // The account addresses are only scanned once to check the balance and select the inputs.
+    address_requests.store(0, Ordering::SeqCst);
+    client
+        .send_if_balance_at_least(&secret_manager, 0, 1_000_000, vec![output])
+        .await
+        .unwrap();
+    assert_eq!(address_requests.load(Ordering::SeqCst), 1);
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn scan_balances_gap_limit() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let addresses = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(0..25)
+        .get_all()
+        .await
+        .unwrap();
+    let used_address = addresses.public[3].clone();
+    let output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let output = basic_output(1_000_000, Address::try_from_bech32(&used_address).unwrap().1);
+    let response = output_response(&output_id, &output, false);
+    let last_scanned_addresses = [addresses.public[23].clone(), addresses.internal[23].clone()];
+    let unscanned_addresses = [addresses.public[24].clone(), addresses.internal[24].clone()];
+    let last_scanned_requests = Arc::new(AtomicUsize::new(0));
+    let last_scanned_requests_ = last_scanned_requests.clone();
+    let unscanned_requests = Arc::new(AtomicUsize::new(0));
+    let unscanned_requests_ = unscanned_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if last_scanned_addresses
+            .iter()
+            .any(|address| path.contains(address.as_str()))
+        {
+            last_scanned_requests_.fetch_add(1, Ordering::SeqCst);
+        }
+        if unscanned_addresses
+            .iter()
+            .any(|address| path.contains(address.as_str()))
+        {
+            unscanned_requests_.fetch_add(1, Ordering::SeqCst);
+        }
+        if path.starts_with(&format!("/api/indexer/v1/outputs/basic?address={used_address}&")) {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [output_id] }).to_string(),
+            )
+        } else if path.starts_with("/api/indexer/v1/outputs/basic?") {
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        } else if path == format!("/api/core/v2/outputs/{output_id}") {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let mut balances = Vec::new();
+    let scan = client
+        .scan_balances(&secret_manager, 0, 20, |address, balance| {
+            balances.push((address.to_string(), balance))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        scan,
+        BalanceScan {
+            total_balance: 1_000_000,
+            highest_used_index: Some(3),
+        }
+    );
+    assert_eq!(balances, [(addresses.public[3].clone(), 1_000_000)]);
+    // The indexes 4 to 23 are empty, so the scan stops before index 24.
+    assert!(last_scanned_requests.load(Ordering::SeqCst) > 0);
+    assert_eq!(unscanned_requests.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn account_output_ids_resume() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let addresses = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(0..6)
+        .finish()
+        .await
+        .unwrap();
+    let first_output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let second_output_id = OutputId::new(TransactionId::new([2; TransactionId::LENGTH]), 0).unwrap();
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let interrupted_ = interrupted.clone();
+    let first_address_requests = Arc::new(AtomicUsize::new(0));
+    let first_address_requests_ = first_address_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        let address_query = |index: usize| format!("/api/indexer/v1/outputs/basic?address={}&", addresses[index]);
+        if path.starts_with(&address_query(0)) {
+            first_address_requests_.fetch_add(1, Ordering::SeqCst);
+        }
+        if path.starts_with(&address_query(2)) {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [first_output_id] }).to_string(),
+            )
+        } else if path.starts_with(&address_query(3)) && !interrupted_.swap(true, Ordering::SeqCst) {
+            (500, String::new())
+        } else if path.starts_with(&address_query(5)) {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [second_output_id] }).to_string(),
+            )
+        } else {
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let cursor = match client.account_output_ids(&secret_manager, ScanCursor::new(0)).await {
+        Err(Error::AddressScanInterrupted { cursor, output_ids, .. }) => {
+            assert_eq!(output_ids, vec![first_output_id]);
+            cursor
+        }
+        other => panic!("expected an interrupted scan, got {other:?}"),
+    };
+    assert_eq!(
+        cursor,
+        ScanCursor {
+            account_index: 0,
+            next_index: 3,
+            empty_count: 0,
+        }
+    );
+    let first_address_requests_before_resume = first_address_requests.load(Ordering::SeqCst);
+
+    // The resumed scan continues at the interrupted address index.
+    assert_eq!(
+        client.account_output_ids(&secret_manager, cursor).await.unwrap(),
+        vec![second_output_id]
+    );
+    assert_eq!(
+        first_address_requests.load(Ordering::SeqCst),
+        first_address_requests_before_resume
+    );
+}
+
+#[tokio::test]
+async fn is_output_owned() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let owned_address = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(3..4)
+        .get_all_raw()
+        .await
+        .unwrap()
+        .public
+        .remove(0);
+    let unrelated_address = Address::Ed25519(Ed25519Address::new([7; 32]));
+
+    let owned_output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let unrelated_output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 1).unwrap();
+    let owned_output_response = output_response(&owned_output_id, &basic_output(1_000_000, owned_address), false);
+    let unrelated_output_response =
+        output_response(&unrelated_output_id, &basic_output(1_000_000, unrelated_address), false);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if path == format!("/api/core/v2/outputs/{owned_output_id}") {
+            (200, owned_output_response.clone())
+        } else if path == format!("/api/core/v2/outputs/{unrelated_output_id}") {
+            (200, unrelated_output_response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert!(
+        client
+            .is_output_owned(&secret_manager, 0, &owned_output_id, 5)
+            .await
+            .unwrap()
+    );
+    // The owning address is outside of the gap limit.
+    assert!(
+        !client
+            .is_output_owned(&secret_manager, 0, &owned_output_id, 3)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !client
+            .is_output_owned(&secret_manager, 0, &unrelated_output_id, 5)
+            .await
+            .unwrap()
+    );
+}
@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod common;
+
 #[cfg(feature = "message_interface")]
 use iota_client::api::GetAddressesBuilderOptions;
 #[cfg(feature = "message_interface")]
@@ -17,11 +19,16 @@ use iota_client::{
     api::GetAddressesBuilder,
     constants::{IOTA_BECH32_HRP, IOTA_COIN_TYPE, IOTA_TESTNET_BECH32_HRP, SHIMMER_BECH32_HRP, SHIMMER_COIN_TYPE},
     secret::{mnemonic::MnemonicSecretManager, SecretManager},
-    Client,
+    Client, Error,
+};
+use iota_types::block::{
+    address::{Address, Ed25519Address},
+    output::RentStructure,
 };
-use iota_types::block::address::Address;
 use serde::{Deserialize, Serialize};
 
+use self::common::mock_node::{mock_node_client_builder, mock_protocol_parameters, spawn_mock_node};
+
 #[tokio::test]
 async fn addresses() {
     let secret_manager = SecretManager::Mnemonic(
@@ -64,6 +71,27 @@ async fn public_key_to_address() {
     );
 }
 
+#[tokio::test]
+async fn hex_to_bech32_batch() {
+    let client = Client::builder().finish().unwrap();
+    let hexes = [
+        "0x96f9de0989e77d0e150e850a5a600e83045fa57419eaf3b20225b763d4e23813",
+        "0x4663a59d128394201a49ec5c48e24c0dc768b9ace029119ab71272fe5ad47eb4",
+        "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ]
+    .map(String::from);
+
+    let addresses = client.hex_to_bech32_batch(&hexes, Some("atoi")).await.unwrap();
+
+    assert_eq!(addresses.len(), 3);
+    for (hex, address) in hexes.iter().zip(addresses) {
+        assert_eq!(address, client.hex_to_bech32(hex, Some("atoi")).await.unwrap());
+    }
+
+    let invalid = client.hex_to_bech32_batch(&["0x00".to_string()], Some("atoi")).await;
+    assert!(invalid.is_err());
+}
+
 #[tokio::test]
 async fn mnemonic_address_generation_iota() {
     let mnemonic = "acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast";
@@ -303,3 +331,29 @@ async fn address_generation() {
         }
     }
 }
+
+#[tokio::test]
+async fn parse_bech32_address_checked() {
+    let url = spawn_mock_node(|_| (404, String::new()));
+    let mut builder = mock_node_client_builder(&url);
+    builder.network_info.protocol_parameters =
+        mock_protocol_parameters("iota", RentStructure::default(), 4_600_000_000_000_000);
+    let client = builder.finish().unwrap();
+    let address = Address::Ed25519(Ed25519Address::new([2; Ed25519Address::LENGTH]));
+
+    match client.parse_bech32_address_checked(&address.to_bech32("rms")).await {
+        Err(Error::Bech32HrpMismatch { expected, found }) => {
+            assert_eq!(expected, "iota");
+            assert_eq!(found, "rms");
+        }
+        _ => panic!("expected Bech32HrpMismatch error"),
+    }
+    assert_eq!(
+        client
+            .parse_bech32_address_checked(&address.to_bech32("iota"))
+            .await
+            .unwrap(),
+        address
+    );
+    assert!(Client::is_address_valid(&address.to_bech32("rms")));
+}
@@ -0,0 +1,728 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use iota_client::{
+    api::{DecodedPayload, RetryCancel},
+    api_types::core::{dto::LedgerInclusionStateDto, response::BlockMetadataResponse},
+    block::{
+        address::{Address, Ed25519Address},
+        input::{Input, UtxoInput},
+        output::{InputsCommitment, OutputId},
+        parent::Parents,
+        payload::{
+            transaction::{RegularTransactionEssence, TransactionEssence, TransactionId},
+            Payload, TaggedDataPayload, TransactionPayload,
+        },
+        protocol::ProtocolParameters,
+        signature::{Ed25519Signature, Signature},
+        unlock::{SignatureUnlock, Unlock, Unlocks},
+        Block, BlockBuilder, BlockDto, BlockId,
+    },
+    BlockEncoding, Client, Error,
+};
+use packable::PackableExt;
+
+use self::common::mock_node::{
+    basic_output, blocks_mock_node_client_builder, info_response, mock_node_client_builder, spawn_blocks_mock_node,
+    spawn_mock_node, spawn_mock_node_with_body,
+};
+
+async fn post_block_with(client: &Client) {
+    client
+        .block()
+        .with_parents(vec![BlockId::null()])
+        .unwrap()
+        .finish()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn post_tagged_data_block() {
+    let (url, content_types) = spawn_blocks_mock_node();
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    let block = client
+        .block()
+        .with_parents(vec![BlockId::null()])
+        .unwrap()
+        .with_tag(b"tag".to_vec())
+        .with_data(b"data".to_vec())
+        .finish()
+        .await
+        .unwrap();
+    assert_eq!(content_types.lock().unwrap().len(), 1);
+
+    let block = client.decode_block(&block.pack_to_vec()).await.unwrap();
+    match block.payload {
+        Some(DecodedPayload::TaggedData { tag, data }) => {
+            assert_eq!(tag, b"tag");
+            assert_eq!(data, b"data");
+        }
+        _ => panic!("expected a tagged data payload"),
+    }
+
+    assert!(matches!(
+        client
+            .block()
+            .with_parents(vec![BlockId::null()])
+            .unwrap()
+            .with_tag(vec![0; 65])
+            .finish()
+            .await,
+        Err(Error::TagTooLong {
+            length: 65,
+            max_length: 64
+        })
+    ));
+    assert_eq!(content_types.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn post_block_too_large() {
+    let (url, content_types) = spawn_blocks_mock_node();
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    // A block with the max data length and a single parent has exactly the max length.
+    let data = vec![0; *TaggedDataPayload::DATA_LENGTH_RANGE.end() as usize];
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .with_payload(Payload::from(TaggedDataPayload::new(Vec::new(), data).unwrap()))
+        .finish()
+        .unwrap();
+    assert_eq!(block.packed_len(), Block::LENGTH_MAX);
+
+    // Deserialized blocks aren't checked, so one with another parent can exceed it.
+    let mut block_json = serde_json::to_value(&block).unwrap();
+    block_json["parents"] =
+        serde_json::to_value(Parents::new(vec![BlockId::null(), BlockId::new([1; 32])]).unwrap()).unwrap();
+    let block: Block = serde_json::from_value(block_json).unwrap();
+
+    for res in [client.post_block(&block).await, client.post_block_raw(&block).await] {
+        match res {
+            Err(Error::BlockTooLarge { size, max }) => {
+                assert_eq!(size, Block::LENGTH_MAX + BlockId::LENGTH);
+                assert_eq!(max, Block::LENGTH_MAX);
+            }
+            res => panic!("expected BlockTooLarge error, got {res:?}"),
+        }
+    }
+    assert!(content_types.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn send_and_read_data_chunked() {
+    // Mock node which stores the posted blocks and returns them by their id, with the max amount of tips, so the chunks
+    // need to leave space for the max amount of parents.
+    let tips = (1..=8)
+        .map(|byte| BlockId::new([byte; BlockId::LENGTH]))
+        .collect::<Vec<_>>();
+    let blocks = Arc::new(Mutex::new(HashMap::<BlockId, Block>::new()));
+    let blocks_ = blocks.clone();
+    let url = spawn_mock_node_with_body(move |head, body| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": tips }).to_string())
+        } else if path == "/api/core/v2/blocks" {
+            let block = Block::try_from_dto_unverified(&serde_json::from_slice::<BlockDto>(body).unwrap()).unwrap();
+            let block_id = block.id();
+            blocks_.lock().unwrap().insert(block_id, block);
+            (201, serde_json::json!({ "blockId": block_id }).to_string())
+        } else if let Some(block) = path
+            .strip_prefix("/api/core/v2/blocks/")
+            .and_then(|block_id| blocks_.lock().unwrap().get(&block_id.parse().unwrap()).cloned())
+        {
+            (200, serde_json::to_string(&BlockDto::from(&block)).unwrap())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url)
+        .with_block_encoding(BlockEncoding::Json)
+        .finish()
+        .unwrap();
+
+    let max_data_length = *TaggedDataPayload::DATA_LENGTH_RANGE.end() as usize;
+    let data = (0..2 * max_data_length + 100).map(|i| i as u8).collect::<Vec<_>>();
+    let block_ids = client.send_data_chunked(b"chunked", &data).await.unwrap();
+    assert_eq!(block_ids.len(), 3);
+    assert_eq!(blocks.lock().unwrap().len(), 3);
+    assert_eq!(client.read_data_chunked(&block_ids[0]).await.unwrap(), data);
+    // Reading can also start at a later chunk.
+    let last_chunk = client.read_data_chunked(&block_ids[2]).await.unwrap();
+    assert!(!last_chunk.is_empty() && data.ends_with(&last_chunk));
+
+    let block_ids = client.send_data_chunked(b"chunked", &[]).await.unwrap();
+    assert_eq!(block_ids.len(), 1);
+    assert!(client.read_data_chunked(&block_ids[0]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn max_parents() {
+    let tips = (1..=8)
+        .map(|byte| BlockId::new([byte; BlockId::LENGTH]))
+        .collect::<Vec<_>>();
+    let offered_tips = Arc::new(AtomicUsize::new(8));
+    let offered_tips_ = offered_tips.clone();
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/tips ") {
+            let tips = &tips[..offered_tips_.load(Ordering::SeqCst)];
+            (200, serde_json::json!({ "tips": tips }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    let block = client.block().with_max_parents(2).finish().await.unwrap();
+    assert_eq!(
+        block.parents().to_vec(),
+        [BlockId::new([1; BlockId::LENGTH]), BlockId::new([2; BlockId::LENGTH])]
+    );
+    // Capped to the maximum amount of parents.
+    let block = client.block().with_max_parents(9).finish().await.unwrap();
+    assert_eq!(block.parents().len(), 8);
+
+    offered_tips.store(1, Ordering::SeqCst);
+    assert!(matches!(
+        client.block().with_max_parents(2).finish().await,
+        Err(Error::InsufficientTips {
+            requested: 2,
+            available: 1
+        })
+    ));
+}
+
+#[tokio::test]
+async fn block_encoding() {
+    let (url, content_types) = spawn_blocks_mock_node();
+
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+    assert_eq!(client.get_block_encoding(), BlockEncoding::Raw);
+    post_block_with(&client).await;
+
+    let client = blocks_mock_node_client_builder(&url)
+        .with_block_encoding(BlockEncoding::Json)
+        .finish()
+        .unwrap();
+    post_block_with(&client).await;
+
+    assert_eq!(
+        *content_types.lock().unwrap(),
+        ["application/vnd.iota.serializer-v1", "application/json"]
+    );
+}
+
+#[tokio::test]
+async fn block_encoding_auto() {
+    let (url, content_types) = spawn_blocks_mock_node();
+
+    let client = blocks_mock_node_client_builder(&url)
+        .with_block_encoding(BlockEncoding::Auto(100))
+        .finish()
+        .unwrap();
+    // A block without payload is smaller than the threshold.
+    post_block_with(&client).await;
+    client
+        .block()
+        .with_parents(vec![BlockId::null()])
+        .unwrap()
+        .with_tag(b"tag".to_vec())
+        .with_data(vec![0; 100])
+        .finish()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *content_types.lock().unwrap(),
+        ["application/json", "application/vnd.iota.serializer-v1"]
+    );
+}
+
+#[tokio::test]
+async fn remote_pow_falls_back_to_local_pow() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .finish()
+        .unwrap();
+
+    // The node can't do the PoW, it rejects the first block and accepts the one with local PoW.
+    let pow_error = r#"{"error":{"code":"400","message":"proof of work is not enabled"}}"#;
+    let posted_blocks = Arc::new(AtomicUsize::new(0));
+    let posted_blocks_ = posted_blocks.clone();
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/tips ") {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            if posted_blocks_.fetch_add(1, Ordering::SeqCst) != 1 {
+                (400, pow_error.to_string())
+            } else {
+                (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+            }
+        } else {
+            (404, String::new())
+        }
+    });
+
+    let client = blocks_mock_node_client_builder(&url)
+        .with_local_pow(false)
+        .finish()
+        .unwrap();
+    assert_eq!(client.post_block(&block).await.unwrap(), BlockId::null());
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 2);
+    assert!(!client.get_local_pow());
+
+    let client = blocks_mock_node_client_builder(&url)
+        .with_local_pow(false)
+        .with_fallback_to_local_pow(false)
+        .finish()
+        .unwrap();
+    assert!(matches!(client.post_block(&block).await, Err(Error::Node(_))));
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 3);
+
+    // A synced node without the PoW feature isn't used for remote PoW at all.
+    let posted_blocks = Arc::new(AtomicUsize::new(0));
+    let posted_blocks_ = posted_blocks.clone();
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/info ") {
+            let mut info: serde_json::Value = serde_json::from_str(&info_response(1, 0)).unwrap();
+            info["protocol"]["minPowScore"] = 0.into();
+            (200, info.to_string())
+        } else if head.starts_with("get /api/core/v2/tips ") {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            posted_blocks_.fetch_add(1, Ordering::SeqCst);
+            (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+        } else {
+            (404, String::new())
+        }
+    });
+
+    let client = Client::builder()
+        .with_node(&url)
+        .unwrap()
+        .with_local_pow(false)
+        .finish()
+        .unwrap();
+    assert_eq!(client.post_block(&block).await.unwrap(), BlockId::null());
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn find_blocks_skips_pruned_blocks() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .finish()
+        .unwrap();
+    let block_id = block.id();
+    let pruned_block_id = BlockId::new([1; BlockId::LENGTH]);
+    let block_json = serde_json::to_string(&BlockDto::from(&block)).unwrap();
+    let block_requests = Arc::new(AtomicUsize::new(0));
+    let block_requests_ = block_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with(&format!("get /api/core/v2/blocks/{block_id} ")) {
+            block_requests_.fetch_add(1, Ordering::SeqCst);
+            (200, block_json.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let blocks = client
+        .find_blocks(&[block_id, pruned_block_id, block_id])
+        .await
+        .unwrap();
+
+    assert_eq!(blocks, [block]);
+    assert_eq!(block_requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn tips_with_metadata() {
+    let referenced_tip = BlockId::new([1; BlockId::LENGTH]);
+    let unreferenced_tip = BlockId::new([2; BlockId::LENGTH]);
+    let metadata = |block_id: BlockId, referenced_by_milestone_index: Option<u32>| BlockMetadataResponse {
+        block_id: block_id.to_string(),
+        parents: vec![BlockId::null().to_string()],
+        is_solid: true,
+        referenced_by_milestone_index,
+        milestone_index: None,
+        ledger_inclusion_state: None,
+        conflict_reason: None,
+        white_flag_index: None,
+        should_promote: None,
+        should_reattach: None,
+    };
+    let referenced_tip_metadata = metadata(referenced_tip, Some(5));
+    let unreferenced_tip_metadata = metadata(unreferenced_tip, None);
+
+    let tips_response = serde_json::json!({ "tips": [referenced_tip, unreferenced_tip] }).to_string();
+    let metadata_responses = HashMap::from([
+        (
+            format!("/api/core/v2/blocks/{referenced_tip}/metadata"),
+            serde_json::to_string(&referenced_tip_metadata).unwrap(),
+        ),
+        (
+            format!("/api/core/v2/blocks/{unreferenced_tip}/metadata"),
+            serde_json::to_string(&unreferenced_tip_metadata).unwrap(),
+        ),
+    ]);
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if path == "/api/core/v2/tips" {
+            (200, tips_response.clone())
+        } else if let Some(response) = metadata_responses.get(path) {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(
+        client.get_tips_with_metadata().await.unwrap(),
+        [
+            (referenced_tip, referenced_tip_metadata),
+            (unreferenced_tip, unreferenced_tip_metadata)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn promote_with_reference() {
+    let tips = [1, 2, 3].map(|byte| BlockId::new([byte; BlockId::LENGTH]));
+    let tips_response = serde_json::json!({ "tips": tips }).to_string();
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/tips ") {
+            (200, tips_response.clone())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+    let block_id = BlockId::new([9; BlockId::LENGTH]);
+
+    let (_, block) = client.promote_with_reference(&block_id, &tips[1]).await.unwrap();
+    assert!(block.parents().iter().any(|parent| parent == &block_id));
+    assert!(block.parents().iter().any(|parent| parent == &tips[1]));
+
+    let unknown_tip = BlockId::new([7; BlockId::LENGTH]);
+    assert!(matches!(
+        client.promote_with_reference(&block_id, &unknown_tip).await,
+        Err(Error::ReferenceNotTip(reference)) if reference == unknown_tip.to_string()
+    ));
+}
+
+#[tokio::test]
+async fn retry_until_included_returns_included_reattachment() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .finish()
+        .unwrap();
+    let block_id = block.id();
+    let block_json = serde_json::to_string(&BlockDto::from(&block)).unwrap();
+    let reattachment_ids = [1, 2].map(|byte| BlockId::new([byte; BlockId::LENGTH]));
+
+    let metadata = |block_id: BlockId, included_by_milestone: Option<u32>| {
+        let metadata = BlockMetadataResponse {
+            block_id: block_id.to_string(),
+            parents: vec![BlockId::null().to_string()],
+            is_solid: true,
+            referenced_by_milestone_index: included_by_milestone,
+            milestone_index: None,
+            ledger_inclusion_state: included_by_milestone.map(|_| LedgerInclusionStateDto::Included),
+            conflict_reason: None,
+            white_flag_index: None,
+            should_promote: None,
+            should_reattach: Some(included_by_milestone.is_none()),
+        };
+        (
+            format!("/api/core/v2/blocks/{block_id}/metadata"),
+            serde_json::to_string(&metadata).unwrap(),
+        )
+    };
+    // The block and its first reattachment need to be reattached, the second reattachment gets included.
+    let metadata_responses = HashMap::from([
+        metadata(block_id, None),
+        metadata(reattachment_ids[0], None),
+        metadata(reattachment_ids[1], Some(5)),
+    ]);
+    let posted_blocks = AtomicUsize::new(0);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some(response) = metadata_responses.get(path) {
+            (200, response.clone())
+        } else if path.starts_with("/api/core/v2/blocks/") {
+            // Reattachments have the same content as the original block.
+            (200, block_json.clone())
+        } else if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            let reattachment_id = reattachment_ids[posted_blocks.fetch_add(1, Ordering::SeqCst)];
+            (201, serde_json::json!({ "blockId": reattachment_id }).to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    let (blocks, milestone_index) = client.retry_until_included(&block_id, Some(0), None).await.unwrap();
+
+    assert_eq!(milestone_index, 5);
+    assert_eq!(
+        blocks.iter().map(|(block_id, _)| *block_id).collect::<Vec<_>>(),
+        [reattachment_ids[1], reattachment_ids[0]]
+    );
+}
+
+#[tokio::test]
+async fn retry_until_included_with_cancel_returns_reattached_blocks() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .finish()
+        .unwrap();
+    let block_id = block.id();
+    let block_json = serde_json::to_string(&BlockDto::from(&block)).unwrap();
+    let reattachment_ids = [1, 2, 3].map(|byte| BlockId::new([byte; BlockId::LENGTH]));
+    let cancel = RetryCancel::new();
+    let cancel_ = cancel.clone();
+    let posted_blocks = AtomicUsize::new(0);
+
+    // No attachment ever gets included, the retrying is cancelled once the first reattachment got posted.
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some(block_id) = path
+            .strip_prefix("/api/core/v2/blocks/")
+            .and_then(|path| path.strip_suffix("/metadata"))
+        {
+            let metadata = BlockMetadataResponse {
+                block_id: block_id.to_string(),
+                parents: vec![BlockId::null().to_string()],
+                is_solid: true,
+                referenced_by_milestone_index: None,
+                milestone_index: None,
+                ledger_inclusion_state: None,
+                conflict_reason: None,
+                white_flag_index: None,
+                should_promote: None,
+                should_reattach: Some(true),
+            };
+            (200, serde_json::to_string(&metadata).unwrap())
+        } else if path.starts_with("/api/core/v2/blocks/") {
+            (200, block_json.clone())
+        } else if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            let reattachment_id = reattachment_ids[posted_blocks.fetch_add(1, Ordering::SeqCst)];
+            cancel_.trigger();
+            (201, serde_json::json!({ "blockId": reattachment_id }).to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    let (blocks, milestone_index) = client
+        .retry_until_included_with_cancel(&block_id, Some(0), None, &cancel)
+        .await
+        .unwrap();
+
+    assert_eq!(milestone_index, None);
+    assert_eq!(
+        blocks.iter().map(|(block_id, _)| *block_id).collect::<Vec<_>>(),
+        [reattachment_ids[0]]
+    );
+}
+
+#[tokio::test]
+async fn retry_until_included_cancel_interrupts_interval() {
+    let client = mock_node_client_builder(&spawn_mock_node(|_| (404, String::new())))
+        .finish()
+        .unwrap();
+    let cancel = RetryCancel::new();
+    let cancel_ = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel_.trigger();
+    });
+
+    let start = Instant::now();
+    let (blocks, milestone_index) = client
+        .retry_until_included_with_cancel(&BlockId::null(), Some(3600), None, &cancel)
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(blocks.is_empty());
+    assert_eq!(milestone_index, None);
+}
+
+#[tokio::test]
+async fn reattach_many() {
+    let blocks = [1, 2, 3].map(|byte| {
+        BlockBuilder::new(Parents::new(vec![BlockId::new([byte; BlockId::LENGTH])]).unwrap())
+            .finish()
+            .unwrap()
+    });
+    let block_ids = blocks.iter().map(Block::id).collect::<Vec<_>>();
+
+    let mut responses = HashMap::new();
+    for (index, block) in blocks.iter().enumerate() {
+        // The second block is already confirmed.
+        let confirmed = index == 1;
+        let metadata = BlockMetadataResponse {
+            block_id: block.id().to_string(),
+            parents: block.parents().iter().map(ToString::to_string).collect(),
+            is_solid: true,
+            referenced_by_milestone_index: confirmed.then_some(5),
+            milestone_index: None,
+            ledger_inclusion_state: confirmed.then_some(LedgerInclusionStateDto::NoTransaction),
+            conflict_reason: None,
+            white_flag_index: None,
+            should_promote: None,
+            should_reattach: Some(!confirmed),
+        };
+        responses.insert(
+            format!("/api/core/v2/blocks/{}/metadata", block.id()),
+            serde_json::to_string(&metadata).unwrap(),
+        );
+        responses.insert(
+            format!("/api/core/v2/blocks/{}", block.id()),
+            serde_json::to_string(&BlockDto::from(block)).unwrap(),
+        );
+    }
+    let posted_blocks = AtomicUsize::new(0);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some(response) = responses.get(path) {
+            (200, response.clone())
+        } else if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            let reattachment_id = BlockId::new([10 + posted_blocks.fetch_add(1, Ordering::SeqCst) as u8; 32]);
+            (201, serde_json::json!({ "blockId": reattachment_id }).to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+
+    let results = client.reattach_many(&block_ids).await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(
+        matches!(&results[1], Err(Error::NoNeedPromoteOrReattach(block_id)) if *block_id == block_ids[1].to_string())
+    );
+    assert!(results[2].is_ok());
+}
+
+#[tokio::test]
+async fn decode_block() {
+    let protocol_parameters = ProtocolParameters::default();
+    let input_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let input = basic_output(
+        1_000_000,
+        Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH])),
+    );
+    let output = basic_output(
+        1_000_000,
+        Address::Ed25519(Ed25519Address::new([2; Ed25519Address::LENGTH])),
+    );
+    let essence =
+        RegularTransactionEssence::builder(protocol_parameters.network_id(), InputsCommitment::new([input].iter()))
+            .with_inputs(vec![Input::Utxo(
+                UtxoInput::new(*input_id.transaction_id(), input_id.index()).unwrap(),
+            )])
+            .with_outputs(vec![output.clone()])
+            .finish(&protocol_parameters)
+            .unwrap();
+    let unlock = Unlock::Signature(SignatureUnlock::new(Signature::Ed25519(Ed25519Signature::new(
+        [0; 32], [0; 64],
+    ))));
+    let transaction = TransactionPayload::new(
+        TransactionEssence::Regular(essence),
+        Unlocks::new(vec![unlock.clone()]).unwrap(),
+    )
+    .unwrap();
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .with_payload(Payload::from(transaction.clone()))
+        .finish()
+        .unwrap();
+
+    let url = spawn_mock_node(|_| (404, String::new()));
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    let decoded = client.decode_block(&block.pack_to_vec()).await.unwrap();
+
+    assert_eq!(decoded.block_id, block.id());
+    assert_eq!(decoded.parents, [BlockId::null()]);
+    match decoded.payload {
+        Some(DecodedPayload::Transaction {
+            transaction_id,
+            inputs,
+            outputs,
+            tagged_data,
+            unlocks,
+            ..
+        }) => {
+            assert_eq!(transaction_id, transaction.id());
+            assert_eq!(
+                inputs,
+                [Input::Utxo(
+                    UtxoInput::new(*input_id.transaction_id(), input_id.index()).unwrap()
+                )]
+            );
+            assert_eq!(outputs, [output]);
+            assert_eq!(tagged_data, None);
+            assert_eq!(unlocks, [unlock]);
+        }
+        _ => panic!("expected a decoded transaction payload"),
+    }
+
+    assert!(client.decode_block(&[0; 8]).await.is_err());
+}
+
+#[tokio::test]
+async fn get_included_block_id() {
+    let transaction_id = TransactionId::new([1; TransactionId::LENGTH]);
+    let block_id = BlockId::new([2; BlockId::LENGTH]);
+    let metadata_path = format!("/api/core/v2/transactions/{transaction_id}/included-block/metadata");
+    let metadata_response = serde_json::json!({
+        "blockId": block_id,
+        "parents": [BlockId::null()],
+        "isSolid": true,
+        "referencedByMilestoneIndex": 5,
+        "ledgerInclusionState": "included",
+    })
+    .to_string();
+    let url = spawn_mock_node(move |head| {
+        if head.split_whitespace().nth(1) == Some(metadata_path.as_str()) {
+            (200, metadata_response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(client.get_included_block_id(&transaction_id).await.unwrap(), block_id);
+    assert!(matches!(
+        client
+            .get_included_block_id(&TransactionId::new([3; TransactionId::LENGTH]))
+            .await,
+        Err(Error::NotFound(_))
+    ));
+}
@@ -0,0 +1,213 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use iota_client::{
+    block::{
+        address::Address,
+        output::{
+            dto::OutputDto, unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output, OutputId,
+            RentStructure, UnlockCondition,
+        },
+        protocol::ProtocolParameters,
+        BlockId,
+    },
+    Client, ClientBuilder,
+};
+
+/// Serves requests with the responses returned by the handler for the request head (request line and headers).
+/// A status of 0 closes the connection without a response, like an unreachable node.
+pub fn spawn_mock_node(handler: impl Fn(&str) -> (u16, String) + Send + 'static) -> String {
+    spawn_mock_node_with_body(move |head, _| handler(head))
+}
+
+/// Like `spawn_mock_node()`, but the handler also gets the request body.
+pub fn spawn_mock_node_with_body(handler: impl Fn(&str, &[u8]) -> (u16, String) + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            let head_end = loop {
+                if let Some(position) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break position + 4;
+                }
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break request.len(),
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_lowercase();
+
+            // Read the body completely before answering, so the connection isn't reset while the client still sends.
+            let content_length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|length| length.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            while request.len() < head_end + content_length {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+
+            let (status, body) = handler(&head, &request[head_end..]);
+            if status == 0 {
+                continue;
+            }
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).ok();
+        }
+    });
+
+    url
+}
+
+/// Mock node which accepts posted blocks and stores the content type they were posted with.
+pub fn spawn_blocks_mock_node() -> (String, Arc<Mutex<Vec<String>>>) {
+    let content_types = Arc::new(Mutex::new(Vec::new()));
+    let content_types_ = content_types.clone();
+
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("post /api/core/v2/blocks ") {
+            let content_type = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-type:"))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            content_types_.lock().unwrap().push(content_type);
+            (201, format!(r#"{{"blockId":"{}"}}"#, BlockId::null()))
+        } else {
+            (404, String::new())
+        }
+    });
+
+    (url, content_types)
+}
+
+/// Mock node which answers the tips endpoint with the given status codes in order and counts the tips requests.
+pub fn spawn_tips_mock_node(tips_statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+    let tips_requests = Arc::new(AtomicUsize::new(0));
+    let tips_requests_ = tips_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/tips ") {
+            let index = tips_requests_.fetch_add(1, Ordering::SeqCst);
+            let status = tips_statuses.get(index).copied().unwrap_or(200);
+            let body = if status == 200 { r#"{"tips":[]}"# } else { "" };
+            (status, body.to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+
+    (url, tips_requests)
+}
+
+/// Client builder for a mock node, which doesn't need to answer the info endpoint.
+pub fn mock_node_client_builder(url: &str) -> ClientBuilder {
+    Client::builder().with_node(url).unwrap().with_ignore_node_health()
+}
+
+/// Client builder for a mock node which doesn't require proof of work, so blocks are finished instantly.
+pub fn blocks_mock_node_client_builder(url: &str) -> ClientBuilder {
+    let mut builder = mock_node_client_builder(url);
+    builder.network_info.protocol_parameters =
+        mock_protocol_parameters("rms", RentStructure::default(), 1_813_620_509_061_365);
+    builder
+}
+
+/// Protocol parameters of a network without proof of work.
+pub fn mock_protocol_parameters(
+    bech32_hrp: &str,
+    rent_structure: RentStructure,
+    token_supply: u64,
+) -> ProtocolParameters {
+    ProtocolParameters::new(
+        2,
+        String::from("mock"),
+        bech32_hrp.to_string(),
+        0,
+        15,
+        rent_structure,
+        token_supply,
+    )
+    .unwrap()
+}
+
+/// Node info of a synced node whose latest milestone has the given index and timestamp.
+pub fn info_response(latest_milestone_index: u32, latest_milestone_timestamp: u32) -> String {
+    serde_json::json!({
+        "name": "HORNET",
+        "version": "2.0.0",
+        "status": {
+            "isHealthy": true,
+            "latestMilestone": { "index": latest_milestone_index, "timestamp": latest_milestone_timestamp },
+            "confirmedMilestone": { "index": latest_milestone_index, "timestamp": latest_milestone_timestamp },
+            "pruningIndex": 0,
+        },
+        "supportedProtocolVersions": [2],
+        "protocol": {
+            "version": 2,
+            "networkName": "shimmer",
+            "bech32Hrp": "smr",
+            "minPowScore": 1500,
+            "belowMaxDepth": 15,
+            "rentStructure": { "vByteCost": 100, "vByteFactorKey": 10, "vByteFactorData": 1 },
+            "tokenSupply": "1813620509061365",
+        },
+        "pendingProtocolParameters": [],
+        "baseToken": {
+            "name": "Shimmer",
+            "tickerSymbol": "SMR",
+            "unit": "SMR",
+            "subunit": "glow",
+            "decimals": 6,
+            "useMetricPrefix": false,
+        },
+        "metrics": { "blocksPerSecond": 0.0, "referencedBlocksPerSecond": 0.0, "referencedRate": 0.0 },
+        "features": [],
+    })
+    .to_string()
+}
+
+/// Basic output with the given amount which is only unlockable by the address.
+pub fn basic_output(amount: u64, address: Address) -> Output {
+    BasicOutputBuilder::new_with_amount(amount)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+        .finish_output(ProtocolParameters::default().token_supply())
+        .unwrap()
+}
+
+/// Response of the outputs endpoint for an output booked in the first milestone.
+pub fn output_response(output_id: &OutputId, output: &Output, is_spent: bool) -> String {
+    serde_json::json!({
+        "metadata": {
+            "blockId": BlockId::null(),
+            "transactionId": output_id.transaction_id(),
+            "outputIndex": output_id.index(),
+            "isSpent": is_spent,
+            "milestoneIndexBooked": 0,
+            "milestoneTimestampBooked": 0,
+            "ledgerIndex": 0,
+        },
+        "output": OutputDto::from(output),
+    })
+    .to_string()
+}
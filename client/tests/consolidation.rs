@@ -0,0 +1,251 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use iota_client::{
+    api::{input_selection::Error as InputSelectionError, ConsolidationPlan, GetAddressesBuilderOptions},
+    block::{
+        address::Address,
+        output::{unlock_condition::TimelockUnlockCondition, BasicOutputBuilder, OutputId, UnlockCondition},
+        payload::{
+            transaction::{TransactionEssence, TransactionId},
+            Payload,
+        },
+        protocol::ProtocolParameters,
+        BlockId, Error as BlockError,
+    },
+    secret::SecretManager,
+    Client, Error,
+};
+
+use self::common::mock_node::{
+    basic_output, blocks_mock_node_client_builder, mock_node_client_builder, output_response, spawn_mock_node,
+};
+
+#[tokio::test]
+async fn preview_consolidation() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let addresses = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(0..3)
+        .finish()
+        .await
+        .unwrap();
+
+    // The first address is empty, the second one has two outputs and the third one a single output.
+    let mut output_ids = HashMap::new();
+    let mut outputs = HashMap::new();
+    for (byte, address_index, amount) in [(1, 1, 1_000_000), (2, 1, 2_000_000), (3, 2, 500_000)] {
+        let output_id = OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap();
+        let output = basic_output(amount, Address::try_from_bech32(&addresses[address_index]).unwrap().1);
+        output_ids
+            .entry(format!(
+                "/api/indexer/v1/outputs/basic?address={}&",
+                addresses[address_index]
+            ))
+            .or_insert_with(Vec::new)
+            .push(output_id);
+        outputs.insert(
+            format!("/api/core/v2/outputs/{output_id}"),
+            output_response(&output_id, &output, false),
+        );
+    }
+    let posted_blocks = Arc::new(AtomicUsize::new(0));
+    let posted_blocks_ = posted_blocks.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some(items) = output_ids
+            .iter()
+            .find_map(|(query, items)| path.starts_with(query).then_some(items))
+        {
+            (200, serde_json::json!({ "ledgerIndex": 0, "items": items }).to_string())
+        } else if path.starts_with("/api/indexer/v1/outputs/basic?") {
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        } else if let Some(response) = outputs.get(path) {
+            (200, response.clone())
+        } else {
+            if head.starts_with("post /api/core/v2/blocks ") {
+                posted_blocks_.fetch_add(1, Ordering::SeqCst);
+            }
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let plan = client
+        .preview_consolidation(
+            &secret_manager,
+            GetAddressesBuilderOptions {
+                range: Some(0..3),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        plan,
+        ConsolidationPlan {
+            input_count: 3,
+            total_amount: 3_500_000,
+            target_address: addresses[0].clone(),
+        }
+    );
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 0);
+
+    assert!(matches!(
+        client
+            .preview_consolidation(
+                &secret_manager,
+                GetAddressesBuilderOptions {
+                    range: Some(0..0),
+                    ..Default::default()
+                },
+            )
+            .await,
+        Err(Error::MissingParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn fan_out() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let addresses = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_bech32_hrp("rms")
+        .with_range(0..3)
+        .finish()
+        .await
+        .unwrap();
+
+    let output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let output = basic_output(10_000_000, Address::try_from_bech32(&addresses[0]).unwrap().1);
+    let response = output_response(&output_id, &output, false);
+    let timelocked_output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 1).unwrap();
+    let timelocked_output = BasicOutputBuilder::from(output.as_basic())
+        .add_unlock_condition(UnlockCondition::Timelock(TimelockUnlockCondition::new(1).unwrap()))
+        .finish_output(ProtocolParameters::default().token_supply())
+        .unwrap();
+    let timelocked_response = output_response(&timelocked_output_id, &timelocked_output, false);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if path == format!("/api/core/v2/outputs/{output_id}") {
+            (200, response.clone())
+        } else if path == format!("/api/core/v2/outputs/{timelocked_output_id}") {
+            (200, timelocked_response.clone())
+        } else if path == "/api/core/v2/tips" {
+            (200, serde_json::json!({ "tips": [BlockId::null()] }).to_string())
+        } else if head.starts_with("post /api/core/v2/blocks ") {
+            (201, serde_json::json!({ "blockId": BlockId::null() }).to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = blocks_mock_node_client_builder(&url).finish().unwrap();
+    let to_addresses = &addresses[1..];
+
+    let block = client
+        .fan_out(&secret_manager, output_id, 4, 1_000_000, to_addresses)
+        .await
+        .unwrap();
+
+    let outputs = match block.payload() {
+        Some(Payload::Transaction(transaction)) => {
+            let TransactionEssence::Regular(essence) = transaction.essence();
+            essence.outputs().to_vec()
+        }
+        _ => panic!("expected a transaction payload"),
+    };
+    let fanned_out_addresses = outputs
+        .iter()
+        .filter(|output| output.amount() == 1_000_000)
+        .map(|output| *output.unlock_conditions().unwrap().address().unwrap().address())
+        .collect::<Vec<_>>();
+    let expected_addresses = [1, 2, 1, 2].map(|index| Address::try_from_bech32(&addresses[index]).unwrap().1);
+    assert_eq!(fanned_out_addresses, expected_addresses);
+    // The rest goes to the remainder.
+    assert_eq!(outputs.len(), 5);
+
+    // Without a remainder, the whole amount can be fanned out.
+    let block = client
+        .fan_out(&secret_manager, output_id, 10, 1_000_000, to_addresses)
+        .await
+        .unwrap();
+    match block.payload() {
+        Some(Payload::Transaction(transaction)) => {
+            let TransactionEssence::Regular(essence) = transaction.essence();
+            assert_eq!(essence.outputs().len(), 10);
+        }
+        _ => panic!("expected a transaction payload"),
+    }
+
+    let remainder_deposit = client
+        .minimum_storage_deposit(&[basic_output(1, Address::try_from_bech32(&addresses[1]).unwrap().1)])
+        .await
+        .unwrap();
+    assert!(matches!(
+        client
+            .fan_out(&secret_manager, output_id, 11, 1_000_000, to_addresses)
+            .await,
+        Err(Error::InputSelection(InputSelectionError::InsufficientAmount {
+            found: 10_000_000,
+            required,
+        })) if required == 11_000_000 + remainder_deposit
+    ));
+    // The remainder of 10_000 doesn't cover its minimum storage deposit.
+    assert!(matches!(
+        client
+            .fan_out(&secret_manager, output_id, 9, 1_110_000, to_addresses)
+            .await,
+        Err(Error::InputSelection(InputSelectionError::InsufficientAmount {
+            found: 10_000_000,
+            required,
+        })) if required == 9_990_000 + remainder_deposit
+    ));
+    assert!(matches!(
+        client
+            .fan_out(&secret_manager, output_id, 2, u64::MAX, to_addresses)
+            .await,
+        Err(Error::Block(BlockError::CreatedAmountOverflow))
+    ));
+    assert!(matches!(
+        client
+            .fan_out(&secret_manager, output_id, 0, 1_000_000, to_addresses)
+            .await,
+        Err(Error::MissingParameter("count"))
+    ));
+    // The created outputs have to cover their minimum storage deposit.
+    assert!(matches!(
+        client.fan_out(&secret_manager, output_id, 2, 1_000, to_addresses).await,
+        Err(Error::Block(BlockError::InsufficientStorageDepositAmount {
+            amount: 1_000,
+            ..
+        }))
+    ));
+    // Outputs with further unlock conditions can't be fanned out.
+    assert!(matches!(
+        client
+            .fan_out(&secret_manager, timelocked_output_id, 2, 1_000_000, to_addresses)
+            .await,
+        Err(Error::Output(_))
+    ));
+}
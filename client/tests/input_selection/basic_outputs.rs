@@ -3,13 +3,15 @@
 
 use std::{collections::HashSet, str::FromStr};
 
+use crypto::keys::slip10::Chain;
 use iota_client::{
-    api::input_selection::{Error, InputSelection, Requirement},
+    api::input_selection::{Error, InputSelection, Requirement, Selected},
     block::{
         address::{Address, AliasAddress, NftAddress},
         output::{AliasId, NftId},
         protocol::protocol_parameters,
     },
+    constants::{HD_WALLET_TYPE, SHIMMER_COIN_TYPE},
 };
 
 use crate::{
@@ -185,6 +187,52 @@ fn input_amount_greater_than_output_amount() {
     });
 }
 
+#[test]
+fn selected_serde_round_trip() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Chain::from_u32_hardened(vec![
+            HD_WALLET_TYPE,
+            SHIMMER_COIN_TYPE,
+            0,
+            0,
+            0,
+        ])),
+    )]);
+    let outputs = build_outputs(vec![Basic(
+        500_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs,
+        outputs,
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .select()
+    .unwrap();
+    assert!(selected.remainder.is_some());
+
+    // The selection can be stored and signed later, for example by an offline signer.
+    let json = serde_json::to_string(&selected).unwrap();
+    assert_eq!(serde_json::from_str::<Selected>(&json).unwrap(), selected);
+}
+
 #[test]
 fn input_amount_greater_than_output_amount_with_remainder_address() {
     let protocol_parameters = protocol_parameters();
@@ -237,6 +285,100 @@ fn input_amount_greater_than_output_amount_with_remainder_address() {
     });
 }
 
+#[test]
+fn input_amount_greater_than_output_amount_without_remainder_address() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    let outputs = build_outputs(vec![Basic(
+        500_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_1]),
+        protocol_parameters,
+    )
+    .select()
+    .unwrap();
+
+    assert!(unsorted_eq(&selected.inputs, &inputs));
+    // The remainder goes back to the address of the input.
+    assert_eq!(selected.outputs.len(), 2);
+    assert!(selected.outputs.contains(&outputs[0]));
+    selected.outputs.iter().for_each(|output| {
+        if !outputs.contains(output) {
+            assert!(is_remainder_or_return(
+                output,
+                1_500_000,
+                BECH32_ADDRESS_ED25519_1,
+                None,
+            ));
+        }
+    });
+}
+
+#[test]
+fn alias_or_nft_remainder_address() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    let outputs = build_outputs(vec![Basic(
+        500_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    for remainder_address in [BECH32_ADDRESS_ALIAS_1, BECH32_ADDRESS_NFT_1] {
+        let remainder_address = Address::try_from_bech32(remainder_address).unwrap().1;
+
+        let selected = InputSelection::new(
+            inputs.clone(),
+            outputs.clone(),
+            addresses(vec![BECH32_ADDRESS_ED25519_0]),
+            protocol_parameters.clone(),
+        )
+        .remainder_address(remainder_address)
+        .select();
+
+        assert!(matches!(
+            selected,
+            Err(Error::InvalidRemainderAddress(address)) if address == remainder_address
+        ));
+    }
+}
+
 #[test]
 fn two_same_inputs_one_needed() {
     let protocol_parameters = protocol_parameters();
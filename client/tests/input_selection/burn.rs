@@ -848,6 +848,99 @@ fn burn_foundry_in_outputs() {
     ));
 }
 
+#[test]
+fn burn_nft_and_foundry_present() {
+    let protocol_parameters = protocol_parameters();
+    let alias_id_1 = AliasId::from_str(ALIAS_ID_1).unwrap();
+    let nft_id_1 = NftId::from_str(NFT_ID_1).unwrap();
+
+    let inputs = build_inputs(vec![
+        Nft(
+            1_000_000,
+            nft_id_1,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Foundry(
+            1_000_000,
+            alias_id_1,
+            0,
+            SimpleTokenScheme::new(U256::from(0), U256::from(0), U256::from(10)).unwrap(),
+            None,
+        ),
+        Alias(
+            1_000_000,
+            alias_id_1,
+            0,
+            BECH32_ADDRESS_ED25519_0,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+    ]);
+    let outputs = build_outputs(vec![Basic(
+        500_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .burn(
+        Burn::new()
+            .add_nft(nft_id_1)
+            .add_foundry(inputs[1].output.as_foundry().id()),
+    )
+    .select()
+    .unwrap();
+
+    // The NFT and the foundry are consumed, the alias controlling the foundry is required to destroy it.
+    assert_eq!(selected.inputs.len(), 3);
+    assert!(selected.inputs.contains(&inputs[0]));
+    assert!(selected.inputs.contains(&inputs[1]));
+    assert!(selected.inputs.contains(&inputs[2]));
+    assert_eq!(selected.outputs.len(), 3);
+    assert!(selected.outputs.contains(&outputs[0]));
+    selected.outputs.iter().for_each(|output| {
+        if !outputs.contains(output) {
+            if output.is_basic() {
+                assert!(is_remainder_or_return(
+                    output,
+                    1_500_000,
+                    BECH32_ADDRESS_ED25519_0,
+                    None,
+                ));
+            } else if output.is_alias() {
+                assert_eq!(output.amount(), 1_000_000);
+                assert_eq!(*output.as_alias().alias_id(), alias_id_1);
+                assert_eq!(
+                    output.as_alias().state_index(),
+                    inputs[2].output.as_alias().state_index() + 1
+                );
+            } else {
+                panic!("unexpected output type")
+            }
+        }
+    });
+}
+
 #[test]
 fn burn_native_tokens() {
     let protocol_parameters = protocol_parameters();
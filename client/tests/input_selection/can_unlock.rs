@@ -0,0 +1,110 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_client::{api::OutputUnlockability, block::output::AliasId, Client};
+
+use crate::{
+    addresses, build_outputs,
+    Build::{Alias, Basic},
+    ALIAS_ID_1, BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1, BECH32_ADDRESS_ED25519_2,
+};
+
+#[test]
+fn can_unlock_timelocked() {
+    let outputs = build_outputs(vec![Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        Some(200),
+        None,
+        None,
+    )]);
+    let address = addresses(vec![BECH32_ADDRESS_ED25519_0])[0];
+
+    assert_eq!(
+        Client::can_unlock(&outputs[0], &address, 100),
+        OutputUnlockability::Locked
+    );
+    assert_eq!(
+        Client::can_unlock(&outputs[0], &address, 200),
+        OutputUnlockability::Unlockable
+    );
+}
+
+#[test]
+fn can_unlock_expired_to_other() {
+    let outputs = build_outputs(vec![Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        Some((BECH32_ADDRESS_ED25519_1, 50)),
+        None,
+    )]);
+    let addresses = addresses(vec![BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1]);
+
+    // Before expiration only the owner can unlock the output.
+    assert!(Client::can_unlock(&outputs[0], &addresses[0], 49).is_unlockable());
+    assert!(!Client::can_unlock(&outputs[0], &addresses[1], 49).is_unlockable());
+    // After expiration only the return address can unlock the output.
+    assert!(!Client::can_unlock(&outputs[0], &addresses[0], 50).is_unlockable());
+    assert!(Client::can_unlock(&outputs[0], &addresses[1], 50).is_unlockable());
+}
+
+#[test]
+fn can_unlock_storage_deposit_return() {
+    let outputs = build_outputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        Some((BECH32_ADDRESS_ED25519_1, 1_000_000)),
+        None,
+        Some((BECH32_ADDRESS_ED25519_1, 50)),
+        None,
+    )]);
+    let addresses = addresses(vec![BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1]);
+
+    assert_eq!(
+        Client::can_unlock(&outputs[0], &addresses[0], 49),
+        OutputUnlockability::UnlockableWithReturn {
+            return_address: addresses[1],
+            amount: 1_000_000,
+        }
+    );
+    // Once expired, the return address gets the whole output and nothing has to be returned.
+    assert_eq!(
+        Client::can_unlock(&outputs[0], &addresses[1], 50),
+        OutputUnlockability::Unlockable
+    );
+}
+
+#[test]
+fn can_unlock_alias_by_state_controller_or_governor() {
+    let outputs = build_outputs(vec![Alias(
+        1_000_000,
+        AliasId::from_str(ALIAS_ID_1).unwrap(),
+        0,
+        BECH32_ADDRESS_ED25519_0,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    let addresses = addresses(vec![
+        BECH32_ADDRESS_ED25519_0,
+        BECH32_ADDRESS_ED25519_1,
+        BECH32_ADDRESS_ED25519_2,
+    ]);
+
+    assert!(Client::can_unlock(&outputs[0], &addresses[0], 0).is_unlockable());
+    assert!(Client::can_unlock(&outputs[0], &addresses[1], 0).is_unlockable());
+    assert!(!Client::can_unlock(&outputs[0], &addresses[2], 0).is_unlockable());
+}
@@ -800,3 +800,118 @@ fn one_nft_output_expiration_expired() {
     assert!(unsorted_eq(&selected.inputs, &inputs));
     assert!(unsorted_eq(&selected.outputs, &outputs));
 }
+
+#[test]
+fn expiration_within_time_skew_tolerance() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        Some((BECH32_ADDRESS_ED25519_0, 98)),
+        None,
+    )]);
+    let outputs = build_outputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs,
+        outputs,
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .timestamp(100)
+    .time_skew_tolerance(5)
+    .select();
+
+    assert!(matches!(selected, Err(Error::NoAvailableInputsProvided)));
+}
+
+#[test]
+fn prefer_expiring_soon() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Basic(
+            1_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_1, 300)),
+            None,
+        ),
+        Basic(
+            1_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_1, 200)),
+            None,
+        ),
+        // Expires the soonest, but is still timelocked.
+        Basic(
+            1_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            Some(150),
+            Some((BECH32_ADDRESS_ED25519_1, 160)),
+            None,
+        ),
+    ]);
+    let outputs = build_outputs(vec![Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_2,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters.clone(),
+    )
+    .timestamp(100)
+    .select()
+    .unwrap();
+
+    // Without the preference, the expiration isn't taken into account.
+    assert_eq!(selected.inputs, vec![inputs[0].clone()]);
+
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .timestamp(100)
+    .prefer_expiring_soon(true)
+    .select()
+    .unwrap();
+
+    assert_eq!(selected.inputs, vec![inputs[2].clone()]);
+    assert!(unsorted_eq(&selected.outputs, &outputs));
+}
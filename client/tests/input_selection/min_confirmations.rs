@@ -0,0 +1,104 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::input_selection::{Error, InputSelection},
+    block::{output::OutputMetadata, protocol::protocol_parameters},
+    secret::types::InputSigningData,
+};
+
+use crate::{
+    addresses, build_inputs, build_outputs, unsorted_eq, Build::Basic, BECH32_ADDRESS_ED25519_0,
+    BECH32_ADDRESS_ED25519_1,
+};
+
+fn booked_at(mut input: InputSigningData, milestone_index_booked: u32, ledger_index: u32) -> InputSigningData {
+    let metadata = &input.output_metadata;
+    input.output_metadata = OutputMetadata::new(
+        *metadata.block_id(),
+        *metadata.output_id(),
+        false,
+        None,
+        None,
+        None,
+        milestone_index_booked,
+        0,
+        ledger_index,
+    );
+    input
+}
+
+#[test]
+fn recently_booked_input_filtered_out() {
+    let protocol_parameters = protocol_parameters();
+
+    let mut inputs = build_inputs(vec![
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+    ]);
+    let old_input = booked_at(inputs.pop().unwrap(), 5, 11);
+    let fresh_input = booked_at(inputs.pop().unwrap(), 10, 11);
+    let outputs = build_outputs(vec![Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        vec![fresh_input, old_input.clone()],
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .min_confirmations(2)
+    .select()
+    .unwrap();
+
+    assert_eq!(selected.inputs, [old_input]);
+    assert!(unsorted_eq(&selected.outputs, &outputs));
+}
+
+#[test]
+fn all_inputs_too_recent() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )])
+    .into_iter()
+    .map(|input| booked_at(input, 10, 11))
+    .collect();
+    let outputs = build_outputs(vec![Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let selected = InputSelection::new(
+        inputs,
+        outputs,
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .min_confirmations(2)
+    .select();
+
+    assert!(matches!(selected, Err(Error::NoAvailableInputsProvided)));
+}
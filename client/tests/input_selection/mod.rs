@@ -6,8 +6,10 @@
 mod alias_outputs;
 mod basic_outputs;
 mod burn;
+mod can_unlock;
 mod expiration;
 mod foundry_outputs;
+mod min_confirmations;
 mod native_tokens;
 mod nft_outputs;
 mod outputs;
@@ -11,7 +11,7 @@ use primitive_types::U256;
 
 use crate::{
     addresses, build_inputs, build_outputs, is_remainder_or_return, unsorted_eq, Build::Basic,
-    BECH32_ADDRESS_ED25519_0, TOKEN_ID_1, TOKEN_ID_2,
+    BECH32_ADDRESS_ED25519_0, TOKEN_ID_1, TOKEN_ID_2, TOKEN_ID_3,
 };
 
 #[test]
@@ -1478,3 +1478,85 @@ fn two_basic_outputs_native_tokens_not_needed() {
 // 200}] }] }] outputs: [basic{ amount: 500_000, native_tokens: [{‘a’: 200}] }]
 // expected selected: [basic{ amount: 1_000_000, native_tokens: [{‘a’: 200}] }]
 // expected remainder: Some(basic{ amount: 500_000 })
+
+#[test]
+fn max_native_tokens_per_remainder() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        Some(vec![(TOKEN_ID_1, 100), (TOKEN_ID_2, 100), (TOKEN_ID_3, 100)]),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    let outputs = build_outputs(vec![Basic(
+        500_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    // All leftover native tokens fit into a single remainder.
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters.clone(),
+    )
+    .select()
+    .unwrap();
+
+    assert!(unsorted_eq(&selected.inputs, &inputs));
+    assert_eq!(selected.outputs.len(), 2);
+    assert!(selected.additional_remainders.is_empty());
+    assert!(is_remainder_or_return(
+        &selected.remainder.unwrap().output,
+        1_500_000,
+        BECH32_ADDRESS_ED25519_0,
+        Some(vec![(TOKEN_ID_1, 100), (TOKEN_ID_2, 100), (TOKEN_ID_3, 100)])
+    ));
+
+    // With at most two native tokens per remainder, the third one goes to another remainder.
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs.clone(),
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .max_native_tokens_per_remainder(2)
+    .select()
+    .unwrap();
+
+    assert!(unsorted_eq(&selected.inputs, &inputs));
+    assert_eq!(selected.outputs.len(), 3);
+    assert!(selected.outputs.contains(&outputs[0]));
+    assert_eq!(
+        selected.outputs.iter().map(|output| output.amount()).sum::<u64>(),
+        2_000_000
+    );
+    let remainder = selected.remainder.unwrap().output;
+    assert_eq!(selected.additional_remainders.len(), 1);
+    let additional_remainder = &selected.additional_remainders[0].output;
+    assert!(selected.outputs.contains(&remainder));
+    assert!(selected.outputs.contains(additional_remainder));
+    assert!(is_remainder_or_return(
+        &remainder,
+        remainder.amount(),
+        BECH32_ADDRESS_ED25519_0,
+        Some(vec![(TOKEN_ID_1, 100), (TOKEN_ID_2, 100)])
+    ));
+    assert!(is_remainder_or_return(
+        additional_remainder,
+        additional_remainder.amount(),
+        BECH32_ADDRESS_ED25519_0,
+        Some(vec![(TOKEN_ID_3, 100)])
+    ));
+}
@@ -3,16 +3,30 @@
 
 #![cfg(feature = "message_interface")]
 
-use std::{env, str::FromStr};
+mod common;
 
+use std::{collections::HashMap, env, str::FromStr};
+
+use crypto::{
+    keys::slip10::Chain,
+    signatures::ed25519::{self, PublicKey},
+};
 use dotenv::dotenv;
 use iota_client::{
     api::GetAddressesBuilderOptions as GenerateAddressesOptions,
-    block::{block::dto::BlockDto, payload::transaction::dto::TransactionEssenceDto, BlockId},
+    block::{
+        address::{Address, Ed25519Address},
+        block::dto::BlockDto,
+        output::OutputId,
+        payload::transaction::{dto::TransactionEssenceDto, TransactionId},
+        BlockId,
+    },
     message_interface::{self, Message, Response},
     secret::SecretManagerDto,
 };
 
+use self::common::mock_node::{basic_output, mock_node_client_builder, output_response, spawn_mock_node};
+
 #[tokio::test]
 async fn generate_addresses() {
     let client_config = r#"{
@@ -263,3 +277,131 @@ async fn hash_transaction_essence() {
         response_type => panic!("Unexpected response type: {response_type:?}"),
     }
 }
+
+#[tokio::test]
+async fn sign_ed25519() {
+    let message_handler = message_interface::create_message_handler(None).unwrap();
+
+    let secret_manager = r#"{"mnemonic":"endorse answer radar about source reunion marriage tag sausage weekend frost daring base attack because joke dream slender leisure group reason prepare broken river"}"#;
+    let message = Message::SignEd25519 {
+        secret_manager: serde_json::from_str(secret_manager).unwrap(),
+        message: format!("0x{}", "01".repeat(32)),
+        chain: Chain::from_u32_hardened(vec![44, 4218, 0, 0, 0]),
+    };
+
+    let response = message_handler.send_message(message).await;
+
+    match response {
+        Response::Ed25519Signature(signature) => {
+            assert_eq!(
+                signature.public_key,
+                "0xe364c1734abe4d60507c03a6f34be01fd1a5530680aae95f7ca573afeb300567"
+            );
+            assert_eq!(
+                signature.signature,
+                "0xac62f9ce817f89e4e9c3dd7641c08fe905c22541d41c87650816637ff762899d584d21ab1b0dcfb90eae8a812873d51ebd590767240bc17b57ffb4e617d9dd02"
+            );
+            let public_key = PublicKey::try_from_bytes(prefix_hex::decode(signature.public_key).unwrap()).unwrap();
+            assert!(public_key.verify(
+                &ed25519::Signature::from_bytes(prefix_hex::decode(signature.signature).unwrap()),
+                &[1; 32]
+            ));
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    let message = Message::SignEd25519 {
+        secret_manager: serde_json::from_str(secret_manager).unwrap(),
+        message: "0x".to_string(),
+        chain: Chain::from_u32_hardened(vec![44, 4218, 0, 0, 0]),
+    };
+
+    match message_handler.send_message(message).await {
+        Response::Error(_) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn verify_ed25519_signature() {
+    let message_handler = message_interface::create_message_handler(None).unwrap();
+    let public_key = "0xe364c1734abe4d60507c03a6f34be01fd1a5530680aae95f7ca573afeb300567";
+    let signature = "0xac62f9ce817f89e4e9c3dd7641c08fe905c22541d41c87650816637ff762899d584d21ab1b0dcfb90eae8a812873d51ebd590767240bc17b57ffb4e617d9dd02";
+    let verify = |public_key: &str, message: String| Message::VerifyEd25519Signature {
+        public_key: public_key.to_string(),
+        signature: signature.to_string(),
+        message,
+    };
+
+    // Signature from the sign_ed25519 test.
+    match message_handler
+        .send_message(verify(public_key, format!("0x{}", "01".repeat(32))))
+        .await
+    {
+        Response::VerifiedEd25519Signature(valid) => assert!(valid),
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Tampered message.
+    match message_handler
+        .send_message(verify(public_key, format!("0x{}02", "01".repeat(31))))
+        .await
+    {
+        Response::VerifiedEd25519Signature(valid) => assert!(!valid),
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Malformed public key.
+    match message_handler
+        .send_message(verify("0xe364c1", format!("0x{}", "01".repeat(32))))
+        .await
+    {
+        Response::Error(_) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn get_outputs_message_batch() {
+    let output_ids = [1, 2].map(|byte| OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap());
+    let mut responses = HashMap::new();
+    for (index, output_id) in output_ids.iter().enumerate() {
+        let output = basic_output(
+            1_000_000 * (index as u64 + 1),
+            Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH])),
+        );
+        let response = output_response(output_id, &output, false);
+        responses.insert(format!("/api/core/v2/outputs/{output_id}"), response);
+    }
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        match responses.get(path) {
+            Some(body) => (200, body.clone()),
+            None => (404, String::new()),
+        }
+    });
+    let message_handler =
+        message_interface::ClientMessageHandler::with_client(mock_node_client_builder(&url).finish().unwrap());
+
+    let response = message_handler
+        .send_message(Message::GetOutputs {
+            output_ids: output_ids.to_vec(),
+        })
+        .await;
+
+    match response {
+        Response::Outputs(outputs) => {
+            let outputs = serde_json::to_value(&outputs).unwrap();
+            let outputs = outputs.as_array().unwrap();
+            assert_eq!(outputs.len(), 2);
+            for (output, output_id) in outputs.iter().zip(output_ids) {
+                assert_eq!(
+                    output["metadata"]["transactionId"],
+                    output_id.transaction_id().to_string()
+                );
+            }
+            assert_eq!(outputs[1]["output"]["amount"], "2000000");
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
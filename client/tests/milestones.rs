@@ -0,0 +1,207 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use futures::StreamExt;
+use iota_client::{
+    block::{
+        address::{Address, Ed25519Address},
+        output::OutputId,
+        payload::{milestone::dto::MilestonePayloadDto, transaction::TransactionId},
+        protocol::ProtocolParameters,
+        rand::payload::rand_milestone_payload,
+    },
+    node_api::core::UtxoChange,
+    Error,
+};
+
+use self::common::mock_node::{basic_output, mock_node_client_builder, output_response, spawn_mock_node};
+
+#[tokio::test]
+async fn utxo_changes_stream() {
+    // Output indexes are limited, so the outputs get distinct transaction ids.
+    let created_outputs = (0..1000u16)
+        .map(|index| {
+            let mut transaction_id = [0; TransactionId::LENGTH];
+            transaction_id[..2].copy_from_slice(&index.to_le_bytes());
+            OutputId::new(TransactionId::new(transaction_id), 0).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let response = serde_json::json!({
+        "index": 5,
+        "createdOutputs": created_outputs.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "consumedOutputs": [],
+    })
+    .to_string();
+
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/core/v2/milestones/by-index/5/utxo-changes ") {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let changes = client
+        .get_utxo_changes_by_index_stream(5)
+        .take(10)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        changes,
+        created_outputs[..10]
+            .iter()
+            .copied()
+            .map(UtxoChange::Created)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn balance_delta() {
+    let owned_address = Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH]));
+    let other_address = Address::Ed25519(Ed25519Address::new([2; Ed25519Address::LENGTH]));
+
+    let mut outputs = HashMap::new();
+    let mut add_output = |index: u8, address: Address, amount: u64| {
+        let output_id = OutputId::new(TransactionId::new([index; TransactionId::LENGTH]), 0).unwrap();
+        let output = basic_output(amount, address);
+        let response = output_response(&output_id, &output, false);
+        outputs.insert(output_id.to_string(), response.to_string());
+        output_id.to_string()
+    };
+    let owned_1 = add_output(1, owned_address, 1_000_000);
+    let other = add_output(2, other_address, 2_000_000);
+    let owned_2 = add_output(3, owned_address, 500_000);
+
+    let utxo_changes = HashMap::from([
+        (
+            11,
+            serde_json::json!({ "index": 11, "createdOutputs": [owned_1, other], "consumedOutputs": [] }).to_string(),
+        ),
+        (
+            12,
+            serde_json::json!({ "index": 12, "createdOutputs": [owned_2], "consumedOutputs": [owned_1] }).to_string(),
+        ),
+    ]);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some(index) = path
+            .strip_prefix("/api/core/v2/milestones/by-index/")
+            .and_then(|path| path.strip_suffix("/utxo-changes"))
+        {
+            if let Some(response) = index.parse().ok().and_then(|index: u32| utxo_changes.get(&index)) {
+                return (200, response.clone());
+            }
+        } else if let Some(response) = path
+            .strip_prefix("/api/core/v2/outputs/")
+            .and_then(|output_id| outputs.get(output_id))
+        {
+            return (200, response.clone());
+        }
+        (404, String::new())
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    let addresses = [owned_address.to_bech32("smr")];
+
+    // Received 1_000_000 and 500_000 in the milestones 11 and 12, but also spent the 1_000_000 in milestone 12.
+    assert_eq!(client.balance_delta(&addresses, 10, 12).await.unwrap(), 500_000);
+    assert_eq!(client.balance_delta(&addresses, 10, 11).await.unwrap(), 1_000_000);
+    assert_eq!(client.balance_delta(&addresses, 11, 12).await.unwrap(), -500_000);
+    // The node doesn't have the data of milestone 10 anymore.
+    assert!(matches!(
+        client.balance_delta(&addresses, 9, 12).await,
+        Err(Error::MilestonePruned(10))
+    ));
+}
+
+#[tokio::test]
+async fn get_milestone_by_index_full_payload() {
+    let milestone = rand_milestone_payload(ProtocolParameters::default().protocol_version());
+    let index = *milestone.essence().index();
+    let milestone_json = serde_json::to_value(MilestonePayloadDto::from(&milestone)).unwrap();
+    let response = milestone_json.to_string();
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with(&format!("get /api/core/v2/milestones/by-index/{index} ")) {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let payload = client.get_milestone_by_index(index).await.unwrap();
+
+    assert_eq!(
+        payload.essence().inclusion_merkle_root().to_string(),
+        milestone_json["inclusionMerkleRoot"]
+    );
+    assert_eq!(
+        payload.essence().applied_merkle_root().to_string(),
+        milestone_json["appliedMerkleRoot"]
+    );
+    assert_eq!(
+        payload.signatures().len(),
+        milestone_json["signatures"].as_array().unwrap().len()
+    );
+    assert_eq!(payload, milestone);
+}
+
+#[tokio::test]
+async fn get_milestone_or_stale() {
+    let milestone = rand_milestone_payload(ProtocolParameters::default().protocol_version());
+    let index = *milestone.essence().index();
+    let milestone_json = serde_json::to_string(&MilestonePayloadDto::from(&milestone)).unwrap();
+    let online = Arc::new(AtomicBool::new(true));
+    let online_ = online.clone();
+    let url = spawn_mock_node(move |head| {
+        if !online_.load(Ordering::SeqCst) {
+            (0, String::new())
+        } else if head.starts_with(&format!("get /api/core/v2/milestones/by-index/{index} ")) {
+            (200, milestone_json.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    // Nothing is cached yet, so the error is returned.
+    online.store(false, Ordering::SeqCst);
+    assert!(matches!(
+        client.get_milestone_by_index_or_stale(index).await,
+        Err(Error::Reqwest(_))
+    ));
+
+    online.store(true, Ordering::SeqCst);
+    let fresh = client.get_milestone_by_index_or_stale(index).await.unwrap();
+    assert!(!fresh.stale);
+    assert_eq!(fresh.value, milestone);
+
+    online.store(false, Ordering::SeqCst);
+    let stale = client.get_milestone_by_index_or_stale(index).await.unwrap();
+    assert!(stale.stale);
+    assert_eq!(stale.value, milestone);
+    let stale = client.get_milestone_by_id_or_stale(&milestone.id()).await.unwrap();
+    assert!(stale.stale);
+    assert_eq!(stale.value, milestone);
+
+    // Errors returned by a node aren't hidden by the cache.
+    online.store(true, Ordering::SeqCst);
+    assert!(matches!(
+        client.get_milestone_by_id_or_stale(&milestone.id()).await,
+        Err(Error::NotFound(_))
+    ));
+}
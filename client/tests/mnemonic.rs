@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::{Client, Result};
+use iota_client::{Client, Error, Result};
 
 #[tokio::test]
 async fn mnemonic() -> Result<()> {
@@ -19,3 +19,23 @@ async fn mnemonic() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn mnemonic_with_word_count() -> Result<()> {
+    let mnemonic = Client::generate_mnemonic_with_word_count(12)?;
+    assert_eq!(mnemonic.split_whitespace().count(), 12);
+    assert!(Client::mnemonic_to_hex_seed(&mnemonic).is_ok());
+
+    for word_count in [15, 18, 21, 24] {
+        let mnemonic = Client::generate_mnemonic_with_word_count(word_count)?;
+        assert_eq!(mnemonic.split_whitespace().count(), word_count);
+        assert!(Client::mnemonic_to_hex_seed(&mnemonic).is_ok());
+    }
+    assert_eq!(Client::generate_mnemonic()?.split_whitespace().count(), 24);
+
+    assert!(matches!(
+        Client::generate_mnemonic_with_word_count(13),
+        Err(Error::InvalidMnemonicLength(13))
+    ));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{mpsc, Mutex},
+    time::Duration,
+};
+
+use iota_client::{
+    mqtt::{BrokerOptions, MqttEvent, MqttPayload, Topic},
+    Client,
+};
+
+const CONNACK: [u8; 4] = [0x20, 0x02, 0x00, 0x00];
+
+// Mock broker that accepts the test connection and the actual connection of the client, then drops the connection once
+// the client subscribed. If the broker restarts, the first packet of the reconnected client is sent to the returned
+// receiver, otherwise the broker goes offline.
+fn spawn_mock_broker(restarts: bool) -> (u16, mpsc::Receiver<u8>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let mut buffer = [0; 1024];
+        for connection in 0..if restarts { 3 } else { 2 } {
+            let (mut stream, _) = listener.accept().unwrap();
+            // CONNECT
+            let _ = stream.read(&mut buffer);
+            stream.write_all(&CONNACK).unwrap();
+            match connection {
+                1 => {
+                    // SUBSCRIBE
+                    let _ = stream.read(&mut buffer);
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                2 if stream.read(&mut buffer).unwrap() > 0 => tx.send(buffer[0]).unwrap(),
+                _ => {}
+            }
+        }
+    });
+
+    (port, rx)
+}
+
+fn mock_broker_client(port: u16, max_reconnection_attempts: usize) -> Client {
+    Client::builder()
+        .with_node(&format!("http://127.0.0.1:{port}"))
+        .unwrap()
+        .with_node_sync_disabled()
+        .with_mqtt_broker_options(
+            BrokerOptions::new()
+                .use_ws(false)
+                .port(port)
+                .max_reconnection_attempts(max_reconnection_attempts),
+        )
+        .finish()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn handlers_are_notified_on_permanent_disconnect() {
+    let (port, _) = spawn_mock_broker(false);
+    let client = mock_broker_client(port, 2);
+
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    client
+        .subscribe(vec![Topic::try_new("milestones").unwrap()], move |event| {
+            tx.lock().unwrap().send(event.clone()).unwrap();
+        })
+        .await
+        .unwrap();
+
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(event.topic, "milestones");
+    assert_eq!(event.payload, MqttPayload::Disconnected);
+    assert_eq!(*client.mqtt_event_receiver().borrow(), MqttEvent::Disconnected);
+}
+
+#[tokio::test]
+async fn subscriptions_resume_after_reconnect() {
+    let (port, first_packet) = spawn_mock_broker(true);
+    let client = mock_broker_client(port, 0);
+
+    client
+        .subscribe(vec![Topic::try_new("milestones").unwrap()], |_| {})
+        .await
+        .unwrap();
+
+    // The client subscribes again to the topic after reconnecting.
+    assert_eq!(first_packet.recv_timeout(Duration::from_secs(10)).unwrap() >> 4, 8);
+}
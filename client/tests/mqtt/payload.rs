@@ -0,0 +1,146 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{mpsc, Mutex},
+    time::Duration,
+};
+
+use iota_client::{
+    api_types::core::response::OutputWithMetadataResponse,
+    block::{
+        address::{Address, Ed25519Address},
+        output::{
+            dto::{OutputDto, OutputMetadataDto},
+            unlock_condition::{AddressUnlockCondition, UnlockCondition},
+            BasicOutputBuilder,
+        },
+        protocol::ProtocolParameters,
+        rand::payload::rand_milestone_payload,
+    },
+    mqtt::{BrokerOptions, MqttPayload, Topic, TopicEvent},
+    Client,
+};
+use packable::PackableExt;
+
+const CONNACK: [u8; 4] = [0x20, 0x02, 0x00, 0x00];
+
+fn publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut remaining_length = 2 + topic.len() + payload.len();
+    let mut packet = vec![0x30];
+    loop {
+        let mut byte = (remaining_length % 128) as u8;
+        remaining_length /= 128;
+        if remaining_length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if remaining_length == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(&(topic.len() as u16).to_be_bytes());
+    packet.extend_from_slice(topic.as_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+// Mock broker that acknowledges every subscription and then publishes the given payload on the given topic.
+fn spawn_publishing_mock_broker(topic: &'static str, payload: Vec<u8>) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let payload = payload.clone();
+            std::thread::spawn(move || {
+                let mut buffer = [0; 1024];
+                // CONNECT
+                let _ = stream.read(&mut buffer);
+                stream.write_all(&CONNACK).unwrap();
+                while let Ok(read) = stream.read(&mut buffer) {
+                    if read == 0 {
+                        break;
+                    }
+                    // SUBSCRIBE
+                    if buffer[0] >> 4 == 8 {
+                        stream.write_all(&[0x90, 0x03, buffer[2], buffer[3], 0x01]).unwrap();
+                        stream.write_all(&publish_packet(topic, &payload)).unwrap();
+                    }
+                }
+            });
+        }
+    });
+
+    port
+}
+
+async fn receive_event(topic: &'static str, payload: Vec<u8>) -> TopicEvent {
+    let port = spawn_publishing_mock_broker(topic, payload);
+    let client = Client::builder()
+        .with_node(&format!("http://127.0.0.1:{port}"))
+        .unwrap()
+        .with_node_sync_disabled()
+        .with_mqtt_broker_options(BrokerOptions::new().use_ws(false).port(port))
+        .finish()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    client
+        .subscribe(vec![Topic::try_new(topic).unwrap()], move |event| {
+            tx.lock().unwrap().send(event.clone()).unwrap();
+        })
+        .await
+        .unwrap();
+
+    rx.recv_timeout(Duration::from_secs(10)).unwrap()
+}
+
+#[tokio::test]
+async fn milestone_payload() {
+    let milestone = rand_milestone_payload(ProtocolParameters::default().protocol_version());
+
+    let event = receive_event("milestones", milestone.pack_to_vec()).await;
+
+    assert_eq!(event.topic, "milestones");
+    assert_eq!(event.payload, MqttPayload::MilestonePayload(milestone));
+}
+
+#[tokio::test]
+async fn output_payload() {
+    let protocol_parameters = ProtocolParameters::default();
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+            Ed25519Address::new([1; 32]),
+        ))))
+        .finish_output(protocol_parameters.token_supply())
+        .unwrap();
+    let response = OutputWithMetadataResponse {
+        metadata: OutputMetadataDto {
+            block_id: format!("0x{}", "11".repeat(32)),
+            transaction_id: format!("0x{}", "22".repeat(32)),
+            output_index: 0,
+            is_spent: false,
+            milestone_index_spent: None,
+            milestone_timestamp_spent: None,
+            transaction_id_spent: None,
+            milestone_index_booked: 1,
+            milestone_timestamp_booked: 1,
+            ledger_index: 1,
+        },
+        output: OutputDto::from(&output),
+    };
+
+    let event = receive_event(
+        "outputs/0x22222222222222222222222222222222222222222222222222222222222222220000",
+        serde_json::to_vec(&response).unwrap(),
+    )
+    .await;
+
+    assert_eq!(event.payload, MqttPayload::Output(response));
+}
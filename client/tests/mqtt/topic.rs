@@ -87,3 +87,24 @@ fn invalid_tags() {
         Err(Error::InvalidTopic(_))
     ));
 }
+
+#[test]
+fn outputs_by_address_topic() {
+    let address = "rms1qr47gz3xxjqpjrwd0yu5glhqrth6w0t08npney8000ust2lcw2r92j5a8rt";
+
+    assert_eq!(
+        Topic::outputs_by_address(address, false).unwrap().topic(),
+        "outputs/unlock/address/rms1qr47gz3xxjqpjrwd0yu5glhqrth6w0t08npney8000ust2lcw2r92j5a8rt"
+    );
+    assert_eq!(
+        Topic::outputs_by_address(address, true).unwrap().topic(),
+        "outputs/unlock/address/rms1qr47gz3xxjqpjrwd0yu5glhqrth6w0t08npney8000ust2lcw2r92j5a8rt/spent"
+    );
+    assert!(matches!(
+        Topic::outputs_by_address(
+            "rms1qr47gz3xxjqpjrwd0yu5glhqrth6w0t08npney8000ust2lcw2r92j5a8rt/",
+            false
+        ),
+        Err(Error::InvalidTopic(_))
+    ));
+}
@@ -8,11 +8,12 @@ mod common;
 use common::{setup_client_with_node_health_ignored, FAUCET_URL, NODE_LOCAL};
 use iota_client::{
     bech32_to_hex, node_api::indexer::query_parameters::QueryParameter, request_funds_from_faucet,
-    secret::SecretManager, Client,
+    secret::SecretManager, Client, Url,
 };
 use iota_types::block::{
     output::OutputId,
     payload::{transaction::TransactionId, Payload},
+    semantic::ConflictReason,
     BlockId,
 };
 
@@ -117,6 +118,18 @@ async fn test_get_info() {
     println!("{r:#?}");
 }
 
+#[ignore]
+#[tokio::test]
+async fn test_get_info_with_url() {
+    let (info, url) = setup_client_with_node_health_ignored()
+        .get_info_with_url()
+        .await
+        .unwrap();
+
+    assert_eq!(url, Url::parse(NODE_LOCAL).unwrap());
+    println!("{info:#?}");
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_get_tips() {
@@ -338,3 +351,93 @@ async fn test_get_included_block() {
 
     println!("{r:#?}");
 }
+
+#[ignore]
+#[tokio::test]
+async fn test_resolve_transaction_inputs() {
+    let (block_id, _transaction_id) = setup_transaction_block().await;
+
+    let r = setup_client_with_node_health_ignored()
+        .resolve_transaction_inputs(&block_id)
+        .await
+        .unwrap();
+
+    assert!(!r.is_empty());
+    for output_response in &r {
+        assert!(output_response.metadata.is_spent);
+    }
+
+    println!("{r:#?}");
+}
+
+#[ignore]
+#[tokio::test]
+async fn test_get_outputs_concurrent() {
+    let (_block_id, transaction_id) = setup_transaction_block().await;
+    let client = setup_client_with_node_health_ignored();
+
+    let output_ids = [
+        OutputId::new(transaction_id, 0).unwrap(),
+        // Doesn't exist, but shouldn't make the other requests fail.
+        OutputId::new(transaction_id, 100).unwrap(),
+        OutputId::new(transaction_id, 1).unwrap(),
+    ];
+
+    let r = client.get_outputs_concurrent(&output_ids, 2).await;
+
+    assert_eq!(r.len(), output_ids.len());
+    assert_eq!(r[0].as_ref().unwrap().metadata.output_index, 0);
+    assert!(r[1].is_err());
+    assert_eq!(r[2].as_ref().unwrap().metadata.output_index, 1);
+}
+
+#[ignore]
+#[tokio::test]
+async fn test_dry_run() {
+    // Make sure the addresses of the development seed have funds.
+    setup_transaction_block().await;
+    let client = setup_client_with_node_health_ignored();
+    let secret_manager = setup_secret_manager();
+
+    let address = client
+        .get_addresses(&secret_manager)
+        .with_range(0..1)
+        .finish()
+        .await
+        .unwrap()
+        .remove(0);
+
+    let prepared_transaction_data = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_output(&address, 1_000_000)
+        .await
+        .unwrap()
+        .prepare_transaction()
+        .await
+        .unwrap();
+
+    let report = client.dry_run(&prepared_transaction_data).await.unwrap();
+    assert!(report.is_valid());
+
+    // Spend the inputs, so the prepared transaction can't be sent anymore.
+    let block_builder = client.block().with_secret_manager(&secret_manager);
+    let payload = block_builder
+        .sign_transaction(prepared_transaction_data.clone())
+        .await
+        .unwrap();
+    let block = block_builder.finish_block(Some(payload)).await.unwrap();
+    client.retry_until_included(&block.id(), None, None).await.unwrap();
+
+    let report = client.dry_run(&prepared_transaction_data).await.unwrap();
+    assert!(!report.is_valid());
+    assert_eq!(report.conflict, ConflictReason::None);
+    assert_eq!(
+        report.unspendable_inputs,
+        prepared_transaction_data
+            .inputs_data
+            .iter()
+            .map(|input| (*input.output_id(), ConflictReason::InputUtxoAlreadySpent))
+            .collect::<Vec<_>>()
+    );
+}
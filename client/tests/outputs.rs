@@ -0,0 +1,457 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use iota_client::{
+    api::NftHistory,
+    block::{
+        address::{Address, AliasAddress, Ed25519Address},
+        input::{Input, UtxoInput},
+        output::{
+            unlock_condition::{AddressUnlockCondition, ImmutableAliasAddressUnlockCondition, UnlockCondition},
+            AliasId, BasicOutputBuilder, FoundryOutputBuilder, InputsCommitment, NftId, NftOutputBuilder, Output,
+            OutputId, RentStructureBuilder, SimpleTokenScheme, TokenId, TokenScheme,
+        },
+        parent::Parents,
+        payload::{
+            transaction::{RegularTransactionEssence, TransactionEssence, TransactionId},
+            Payload, TransactionPayload,
+        },
+        protocol::ProtocolParameters,
+        signature::{Ed25519Signature, Signature},
+        unlock::{SignatureUnlock, Unlock, Unlocks},
+        BlockBuilder, BlockDto, BlockId,
+    },
+    node_api::indexer::query_parameters::QueryParameter,
+    Client, Error,
+};
+use primitive_types::U256;
+
+use self::common::mock_node::{
+    basic_output, mock_node_client_builder, mock_protocol_parameters, output_response, spawn_mock_node,
+};
+
+#[tokio::test]
+async fn output_cache() {
+    let transaction_id = TransactionId::new([1; TransactionId::LENGTH]);
+    let spent_output_id = OutputId::new(transaction_id, 0).unwrap();
+    let unspent_output_id = OutputId::new(transaction_id, 1).unwrap();
+    let output = basic_output(
+        1_000_000,
+        Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH])),
+    );
+    let output_requests = Arc::new(Mutex::new(HashMap::<OutputId, usize>::new()));
+    let output_requests_ = output_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        match path.strip_prefix("/api/core/v2/outputs/").map(OutputId::from_str) {
+            Some(Ok(output_id)) => {
+                *output_requests_.lock().unwrap().entry(output_id).or_default() += 1;
+                (200, output_response(&output_id, &output, output_id == spent_output_id))
+            }
+            _ => (404, String::new()),
+        }
+    });
+    // Unspent outputs expire right away.
+    let client = mock_node_client_builder(&url)
+        .with_output_cache(10, Duration::ZERO)
+        .finish()
+        .unwrap();
+
+    for _ in 0..2 {
+        assert!(client.get_output(&spent_output_id).await.unwrap().metadata.is_spent);
+        assert!(!client.get_output(&unspent_output_id).await.unwrap().metadata.is_spent);
+    }
+    assert_eq!(output_requests.lock().unwrap()[&spent_output_id], 1);
+    assert_eq!(output_requests.lock().unwrap()[&unspent_output_id], 2);
+
+    client.clear_output_cache().unwrap();
+    client.get_output(&spent_output_id).await.unwrap();
+    assert_eq!(output_requests.lock().unwrap()[&spent_output_id], 2);
+}
+
+#[tokio::test]
+async fn foundries_of_alias() {
+    let alias_id = AliasId::new([1; AliasId::LENGTH]);
+    let foundry_ids = [1, 2].map(|byte| OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap());
+    // The request head is lowercased by the mock node.
+    let expected_path = format!(
+        "/api/indexer/v1/outputs/foundry?aliasaddress={}",
+        Address::Alias(AliasAddress::new(alias_id)).to_bech32("smr")
+    );
+    let foundries_response = serde_json::json!({ "ledgerIndex": 0, "items": foundry_ids }).to_string();
+    let url = spawn_mock_node(move |head| {
+        if head.split(' ').nth(1) == Some(expected_path.as_str()) {
+            (200, foundries_response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(client.foundries_of_alias(alias_id).await.unwrap(), foundry_ids);
+}
+
+#[tokio::test]
+async fn native_token_supply() {
+    let foundry_output = FoundryOutputBuilder::new_with_amount(
+        1_000_000,
+        1,
+        TokenScheme::Simple(SimpleTokenScheme::new(U256::from(100), U256::from(30), U256::from(1000)).unwrap()),
+    )
+    .unwrap()
+    .add_unlock_condition(UnlockCondition::ImmutableAliasAddress(
+        ImmutableAliasAddressUnlockCondition::new(AliasAddress::new(AliasId::new([1; AliasId::LENGTH]))),
+    ))
+    .finish(ProtocolParameters::default().token_supply())
+    .unwrap();
+    let foundry_id = foundry_output.id();
+    let output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let response = output_response(&output_id, &Output::Foundry(foundry_output), false);
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if path == format!("/api/indexer/v1/outputs/foundry/{foundry_id}") {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [output_id] }).to_string(),
+            )
+        } else if path == format!("/api/core/v2/outputs/{output_id}") {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(
+        client.native_token_supply(TokenId::from(foundry_id)).await.unwrap(),
+        U256::from(70)
+    );
+}
+
+// Mock node with the history of an NFT that got minted and then transferred three times.
+fn spawn_nft_history_mock_node(pruned_mint: bool) -> (String, NftId, [OutputId; 4]) {
+    let protocol_parameters = ProtocolParameters::default();
+    let address = Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH]));
+    let output_ids =
+        [1, 2, 3, 4].map(|byte| OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap());
+    let nft_id = NftId::from(&output_ids[0]);
+    let nft_output = |nft_id: NftId| {
+        NftOutputBuilder::new_with_amount(1_000_000, nft_id)
+            .unwrap()
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+            .finish_output(protocol_parameters.token_supply())
+            .unwrap()
+    };
+
+    let mut responses = HashMap::new();
+    responses.insert(
+        format!("/api/indexer/v1/outputs/nft/{nft_id}"),
+        serde_json::json!({ "ledgerIndex": 0, "items": [output_ids[3]] }).to_string(),
+    );
+    for (index, output_id) in output_ids.iter().enumerate() {
+        let output = nft_output(if index == 0 { NftId::null() } else { nft_id });
+        if index != 0 || !pruned_mint {
+            let response = output_response(output_id, &output, index != 3);
+            responses.insert(format!("/api/core/v2/outputs/{output_id}"), response);
+        }
+        // The transaction that transferred the NFT from the previous output to this one.
+        if index != 0 {
+            let input_id = output_ids[index - 1];
+            let essence = RegularTransactionEssence::builder(
+                protocol_parameters.network_id(),
+                InputsCommitment::new([nft_output(nft_id)].iter()),
+            )
+            .with_inputs(vec![Input::Utxo(
+                UtxoInput::new(*input_id.transaction_id(), input_id.index()).unwrap(),
+            )])
+            .with_outputs(vec![output])
+            .finish(&protocol_parameters)
+            .unwrap();
+            let unlocks = Unlocks::new(vec![Unlock::Signature(SignatureUnlock::new(Signature::Ed25519(
+                Ed25519Signature::new([0; 32], [0; 64]),
+            )))])
+            .unwrap();
+            let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+                .with_payload(Payload::from(
+                    TransactionPayload::new(TransactionEssence::Regular(essence), unlocks).unwrap(),
+                ))
+                .finish()
+                .unwrap();
+            responses.insert(
+                format!(
+                    "/api/core/v2/transactions/{}/included-block",
+                    output_id.transaction_id()
+                ),
+                serde_json::to_string(&BlockDto::from(&block)).unwrap(),
+            );
+        }
+    }
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        responses
+            .get(path)
+            .map_or_else(|| (404, String::new()), |response| (200, response.clone()))
+    });
+
+    (url, nft_id, output_ids)
+}
+
+#[tokio::test]
+async fn nft_history() {
+    let (url, nft_id, output_ids) = spawn_nft_history_mock_node(false);
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(
+        client.nft_history(nft_id).await.unwrap(),
+        NftHistory {
+            output_ids: output_ids.to_vec(),
+            complete: true,
+        }
+    );
+
+    // The minting output was pruned, so the history can only be followed back to the first transfer.
+    let (url, nft_id, output_ids) = spawn_nft_history_mock_node(true);
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert_eq!(
+        client.nft_history(nft_id).await.unwrap(),
+        NftHistory {
+            output_ids: output_ids[1..].to_vec(),
+            complete: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn basic_output_ids_pages() {
+    let output_ids = [1, 2, 3].map(|byte| OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap());
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        if path == "/api/indexer/v1/outputs/basic?hastimelock=false" {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "cursor": "next.2", "items": [output_ids[0], output_ids[1]] })
+                    .to_string(),
+            )
+        } else if path == "/api/indexer/v1/outputs/basic?cursor=next.2&hastimelock=false" {
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [output_ids[2]] }).to_string(),
+            )
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    let query_parameters = vec![QueryParameter::HasTimelock(false)];
+
+    let (first_page, cursor) = client
+        .basic_output_ids_paged(query_parameters.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(first_page, output_ids[..2]);
+    assert_eq!(cursor.as_deref(), Some("next.2"));
+
+    let (second_page, cursor) = client
+        .basic_output_ids_paged(query_parameters.clone(), cursor)
+        .await
+        .unwrap();
+    assert_eq!(second_page, output_ids[2..]);
+    assert_eq!(cursor, None);
+
+    // The bounded variant errors if the indexer has more pages than allowed.
+    assert_eq!(
+        client.basic_output_ids_all(query_parameters.clone(), 2).await.unwrap(),
+        output_ids
+    );
+    assert!(matches!(
+        client.basic_output_ids_all(query_parameters.clone(), 1).await,
+        Err(Error::OutputIdsPageLimit { max_pages: 1 })
+    ));
+
+    // Without a cursor, all pages are collected.
+    let response = client.basic_output_ids(query_parameters).await.unwrap();
+    assert_eq!(response.items, output_ids);
+    assert_eq!(response.cursor, None);
+}
+
+#[tokio::test]
+async fn address_has_spent_outputs() {
+    let addresses = [1, 2].map(|byte| Address::Ed25519(Ed25519Address::new([byte; Ed25519Address::LENGTH])));
+    let [spent_address, unspent_address] = addresses.map(|address| address.to_bech32("smr"));
+    let ledger_updates_requests = Arc::new(AtomicUsize::new(0));
+    let ledger_updates_requests_ = ledger_updates_requests.clone();
+    let spent_path = format!("/api/explorer/v2/ledger/updates/by-address/{spent_address}");
+    let unspent_path = format!("/api/explorer/v2/ledger/updates/by-address/{unspent_address}");
+
+    // Mock permanode on which the output of the first address got spent, on the second page of its ledger updates.
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        if path == spent_path {
+            ledger_updates_requests_.fetch_add(1, Ordering::SeqCst);
+            if query.contains("cursor=next") {
+                (200, r#"{"items":[{"isSpent":true}]}"#.to_string())
+            } else {
+                (200, r#"{"items":[{"isSpent":false}],"cursor":"next"}"#.to_string())
+            }
+        } else if path == unspent_path {
+            (200, r#"{"items":[{"isSpent":false}]}"#.to_string())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    assert!(client.address_has_spent_outputs(&spent_address).await.unwrap());
+    assert_eq!(ledger_updates_requests.load(Ordering::SeqCst), 2);
+    assert!(!client.address_has_spent_outputs(&unspent_address).await.unwrap());
+
+    // A node without the explorer API can't tell.
+    let url = spawn_mock_node(|_| (404, String::new()));
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    assert!(matches!(
+        client.address_has_spent_outputs(&spent_address).await,
+        Err(Error::FeatureNotSupportedByNode(_))
+    ));
+}
+
+#[tokio::test]
+async fn outputs_for_addresses() {
+    let addresses = [1, 2, 3].map(|byte| Address::Ed25519(Ed25519Address::new([byte; Ed25519Address::LENGTH])));
+    let bech32_addresses = addresses.map(|address| address.to_bech32("smr"));
+
+    let mut outputs = HashMap::new();
+    let mut add_output = |index: u8, address: Address| {
+        let output_id = OutputId::new(TransactionId::new([index; TransactionId::LENGTH]), 0).unwrap();
+        let output = basic_output(1_000_000, address);
+        let response = output_response(&output_id, &output, false);
+        outputs.insert(output_id.to_string(), response.to_string());
+        output_id
+    };
+    let address_output_ids = [
+        vec![add_output(1, addresses[0]), add_output(2, addresses[0])],
+        vec![add_output(3, addresses[1])],
+        vec![],
+    ];
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_ = requests.clone();
+    let indexer_responses = bech32_addresses
+        .iter()
+        .zip(&address_output_ids)
+        .map(|(address, output_ids)| {
+            (
+                format!("/api/indexer/v1/outputs/basic?address={address}&hasstoragedepositreturn=false"),
+                serde_json::json!({ "ledgerIndex": 0, "items": output_ids }).to_string(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let empty_response = serde_json::json!({ "ledgerIndex": 0, "items": [] }).to_string();
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default().to_string();
+        requests_.lock().unwrap().push(path.clone());
+        if let Some(response) = indexer_responses.get(&path) {
+            (200, response.clone())
+        } else if path.starts_with("/api/indexer/v1/outputs/basic?expirationreturnaddress=") {
+            (200, empty_response.clone())
+        } else if let Some(response) = path
+            .strip_prefix("/api/core/v2/outputs/")
+            .and_then(|output_id| outputs.get(output_id))
+        {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    // The first address is passed twice, but only queried once.
+    let mut query_addresses = bech32_addresses.to_vec();
+    query_addresses.push(bech32_addresses[0].clone());
+    let address_outputs = client.outputs_for_addresses(&query_addresses).await.unwrap();
+
+    assert_eq!(address_outputs.len(), 3);
+    for (address, output_ids) in bech32_addresses.iter().zip(&address_output_ids) {
+        let found_output_ids = address_outputs[address]
+            .iter()
+            .map(|output| output.metadata.output_id().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(&found_output_ids, output_ids);
+    }
+
+    let requests = requests.lock().unwrap();
+    for address in &bech32_addresses {
+        let address_requests = requests.iter().filter(|path| path.contains(address.as_str())).count();
+        // One request for the outputs owned by the address and one for the expired outputs returned to it.
+        assert_eq!(address_requests, 2);
+    }
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|path| path.starts_with("/api/core/v2/outputs/"))
+            .count(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn minimum_storage_deposit() {
+    let token_supply = ProtocolParameters::default().token_supply();
+    let mut builder = Client::builder().with_node_sync_disabled();
+    builder.network_info.protocol_parameters = mock_protocol_parameters(
+        "rms",
+        RentStructureBuilder::new()
+            .byte_cost(100)
+            .byte_factor_key(10)
+            .byte_factor_data(1)
+            .finish(),
+        token_supply,
+    );
+    let client = builder.finish().unwrap();
+    let address_unlock_condition = UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+        Ed25519Address::new([1; 32]),
+    )));
+    let basic_output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .unwrap()
+        .add_unlock_condition(address_unlock_condition.clone())
+        .finish_output(token_supply)
+        .unwrap();
+    let nft_output = NftOutputBuilder::new_with_amount(1_000_000, NftId::null())
+        .unwrap()
+        .add_unlock_condition(address_unlock_condition)
+        .finish_output(token_supply)
+        .unwrap();
+
+    assert_eq!(
+        client
+            .minimum_storage_deposit(std::slice::from_ref(&basic_output))
+            .await
+            .unwrap(),
+        42_600
+    );
+    // 42_600 for the basic output and 45_900 for the NFT output.
+    assert_eq!(
+        client
+            .minimum_storage_deposit(&[basic_output, nft_output])
+            .await
+            .unwrap(),
+        88_500
+    );
+    assert_eq!(client.minimum_storage_deposit(&[]).await.unwrap(), 0);
+}
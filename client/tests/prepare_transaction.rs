@@ -0,0 +1,371 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use iota_client::{
+    api::{
+        input_selection::Error as InputSelectionError, sign_prepared_transaction, ClientBlockBuilderOptions,
+        PreparedTransactionData,
+    },
+    block::{
+        address::{Address, Ed25519Address},
+        input::{dto::UtxoInputDto, UtxoInput},
+        output::{BasicOutputBuilder, NativeToken, OutputId, TokenId},
+        payload::transaction::{RegularTransactionEssence, TransactionEssence, TransactionId},
+        semantic::ConflictReason,
+    },
+    secret::SecretManager,
+    Client, Error,
+};
+use primitive_types::U256;
+
+use self::common::mock_node::{basic_output, mock_node_client_builder, output_response, spawn_mock_node};
+
+#[tokio::test]
+async fn input_selection_skips_outputs_of_empty_addresses() {
+    let indexer_requests = Arc::new(AtomicUsize::new(0));
+    let indexer_requests_ = indexer_requests.clone();
+    let output_requests = Arc::new(AtomicUsize::new(0));
+    let output_requests_ = output_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        if head.starts_with("get /api/indexer/v1/outputs/basic?") {
+            indexer_requests_.fetch_add(1, Ordering::SeqCst);
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        } else {
+            if head.starts_with("get /api/core/v2/outputs/") {
+                output_requests_.fetch_add(1, Ordering::SeqCst);
+            }
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let address = client
+        .get_addresses(&secret_manager)
+        .with_range(0..1)
+        .finish()
+        .await
+        .unwrap()
+        .remove(0);
+
+    // All addresses are empty, so the inputs can't be found.
+    assert!(
+        client
+            .block()
+            .with_secret_manager(&secret_manager)
+            .with_output(&address, 1_000_000)
+            .await
+            .unwrap()
+            .prepare_transaction()
+            .await
+            .is_err()
+    );
+
+    // Two output id queries for each of the 20 public and 20 internal addresses of the gap range, but no requests for
+    // outputs.
+    assert_eq!(indexer_requests.load(Ordering::SeqCst), 80);
+    assert_eq!(output_requests.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn input_consolidation_threshold() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let address = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(0..1)
+        .finish()
+        .await
+        .unwrap()
+        .remove(0);
+
+    let mut outputs = HashMap::new();
+    let output_ids = [1, 2, 3].map(|index| {
+        let output_id = OutputId::new(TransactionId::new([index; TransactionId::LENGTH]), 0).unwrap();
+        let output = basic_output(1_000_000, Address::try_from_bech32(&address).unwrap().1);
+        let response = output_response(&output_id, &output, false);
+        outputs.insert(output_id.to_string(), response.to_string());
+        output_id
+    });
+    let address_query = format!("/api/indexer/v1/outputs/basic?address={address}&hasstoragedepositreturn=false");
+    let address_response = serde_json::json!({ "ledgerIndex": 0, "items": output_ids }).to_string();
+    let url = spawn_mock_node(move |head| {
+        let path = head.split_whitespace().nth(1).unwrap_or_default();
+        if path == address_query {
+            (200, address_response.clone())
+        } else if path.starts_with("/api/indexer/v1/outputs/basic?") {
+            (200, r#"{"ledgerIndex":0,"items":[]}"#.to_string())
+        } else if let Some(response) = path
+            .strip_prefix("/api/core/v2/outputs/")
+            .and_then(|output_id| outputs.get(output_id))
+        {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url)
+        .with_input_consolidation_threshold(2)
+        .finish()
+        .unwrap();
+    assert_eq!(client.get_consolidation_threshold(), 2);
+
+    // All three outputs of the address are needed for the amount.
+    let result = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_output(&address, 2_500_000)
+        .await
+        .unwrap()
+        .prepare_transaction()
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::ConsolidationRequired { limit: 2, needed: 3 })
+    ));
+
+    // Custom inputs are checked against the threshold as well.
+    let result = client
+        .block()
+        .with_input(UtxoInput::from(output_ids[0]))
+        .unwrap()
+        .with_input(UtxoInput::from(output_ids[1]))
+        .unwrap()
+        .with_input(UtxoInput::from(output_ids[2]));
+    assert!(matches!(
+        result,
+        Err(Error::ConsolidationRequired { limit: 2, needed: 3 })
+    ));
+
+    // The threshold can't exceed the maximum amount of inputs of a transaction.
+    let client = mock_node_client_builder(&url)
+        .with_input_consolidation_threshold(1_000)
+        .finish()
+        .unwrap();
+    assert_eq!(client.get_consolidation_threshold(), 128);
+}
+
+#[tokio::test]
+async fn prepare_and_sign_offline() {
+    let secret_manager =
+        SecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2").unwrap();
+    let address = Client::builder()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap()
+        .get_addresses(&secret_manager)
+        .with_range(0..1)
+        .finish()
+        .await
+        .unwrap()
+        .remove(0);
+
+    let input_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let input = basic_output(2_000_000, Address::try_from_bech32(&address).unwrap().1);
+    let input_response = output_response(&input_id, &input, false);
+    let input_path = format!("/api/core/v2/outputs/{input_id}");
+    let url = spawn_mock_node(move |head| {
+        if head.split(' ').nth(1) == Some(input_path.as_str()) {
+            (200, input_response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let options: ClientBlockBuilderOptions = serde_json::from_value(serde_json::json!({
+        "inputs": [UtxoInputDto::from(&UtxoInput::from(input_id))],
+        "output": { "address": address, "amount": "1000000" },
+    }))
+    .unwrap();
+    let prepared_transaction_data = client
+        .prepare_transaction(Some(&secret_manager), options)
+        .await
+        .unwrap();
+
+    // Moved to an air-gapped machine as JSON, the chain of the input included.
+    let json = serde_json::to_string(&prepared_transaction_data).unwrap();
+    let transferred: PreparedTransactionData = serde_json::from_str(&json).unwrap();
+    assert_eq!(transferred, prepared_transaction_data);
+    assert!(transferred.inputs_data[0].chain.is_some());
+
+    drop(client);
+    let transaction = sign_prepared_transaction(&transferred, &secret_manager).await.unwrap();
+    assert_eq!(*transaction.essence(), prepared_transaction_data.essence);
+    assert_eq!(transaction.unlocks().len(), 1);
+}
+
+#[tokio::test]
+async fn output_tag() {
+    let input_address = Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH]));
+    let recipient_address = Address::Ed25519(Ed25519Address::new([2; Ed25519Address::LENGTH]));
+
+    let input_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let input = basic_output(2_000_000, input_address);
+    let input_response = output_response(&input_id, &input, false);
+    let input_path = format!("/api/core/v2/outputs/{input_id}");
+
+    let url = spawn_mock_node(move |head| {
+        if head.split(' ').nth(1) == Some(input_path.as_str()) {
+            (200, input_response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let prepared_transaction = client
+        .block()
+        .with_input(UtxoInput::new(*input_id.transaction_id(), input_id.index()).unwrap())
+        .unwrap()
+        .with_output_tag(b"category".to_vec())
+        .unwrap()
+        .with_output(&recipient_address.to_bech32("smr"), 1_000_000)
+        .await
+        .unwrap()
+        .prepare_transaction()
+        .await
+        .unwrap();
+    let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
+    let tagged_outputs = essence
+        .outputs()
+        .iter()
+        .filter(|output| {
+            output
+                .features()
+                .and_then(|features| features.tag())
+                .map(|tag| tag.tag())
+                == Some(b"category".as_slice())
+        })
+        .collect::<Vec<_>>();
+    // Only the created output is tagged, not the remainder.
+    assert_eq!(tagged_outputs.len(), 1);
+    assert_eq!(tagged_outputs[0].amount(), 1_000_000);
+
+    assert!(matches!(
+        client.block().with_output_tag(vec![0; 65]),
+        Err(Error::Block(_))
+    ));
+}
+
+#[tokio::test]
+async fn find_inputs_stops_when_covered() {
+    let addresses = [1, 2, 3].map(|byte| Address::Ed25519(Ed25519Address::new([byte; Ed25519Address::LENGTH])));
+    let mut output_ids = HashMap::new();
+    let mut outputs = HashMap::new();
+    for (byte, (address, amount)) in (1..).zip(addresses.iter().zip([1_000_000, 2_000_000, 5_000_000])) {
+        let output_id = OutputId::new(TransactionId::new([byte; TransactionId::LENGTH]), 0).unwrap();
+        let output = basic_output(amount, *address);
+        output_ids.insert(
+            format!("/api/indexer/v1/outputs/basic?address={}&", address.to_bech32("smr")),
+            output_id,
+        );
+        outputs.insert(
+            format!("/api/core/v2/outputs/{output_id}"),
+            output_response(&output_id, &output, false),
+        );
+    }
+    let indexer_requests = Arc::new(AtomicUsize::new(0));
+    let indexer_requests_ = indexer_requests.clone();
+
+    let url = spawn_mock_node(move |head| {
+        let path = head.split(' ').nth(1).unwrap_or_default();
+        if let Some((_, output_id)) = output_ids.iter().find(|(query, _)| path.starts_with(query.as_str())) {
+            indexer_requests_.fetch_add(1, Ordering::SeqCst);
+            (
+                200,
+                serde_json::json!({ "ledgerIndex": 0, "items": [output_id] }).to_string(),
+            )
+        } else if let Some(response) = outputs.get(path) {
+            (200, response.clone())
+        } else {
+            (404, String::new())
+        }
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+    let addresses = addresses.map(|address| address.to_bech32("smr")).to_vec();
+
+    // Covered by the first two addresses, so the third one isn't queried.
+    let inputs = client.find_inputs(addresses.clone(), 2_500_000).await.unwrap();
+    assert_eq!(
+        inputs,
+        [
+            UtxoInput::new(TransactionId::new([2; TransactionId::LENGTH]), 0).unwrap(),
+            UtxoInput::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap(),
+        ]
+    );
+    assert_eq!(indexer_requests.load(Ordering::SeqCst), 2);
+
+    assert!(matches!(
+        client.find_inputs(addresses, 10_000_000).await,
+        Err(Error::InputSelection(InputSelectionError::InsufficientAmount {
+            found: 8_000_000,
+            required: 10_000_000
+        }))
+    ));
+}
+
+#[tokio::test]
+async fn dry_run_validates_unsigned_transaction() {
+    let input_address = Address::Ed25519(Ed25519Address::new([1; Ed25519Address::LENGTH]));
+    let recipient_address = Address::Ed25519(Ed25519Address::new([2; Ed25519Address::LENGTH]));
+
+    let input_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), 0).unwrap();
+    let input = basic_output(2_000_000, input_address);
+    let input_response = output_response(&input_id, &input, false);
+    let metadata_response = serde_json::from_str::<serde_json::Value>(&input_response).unwrap()["metadata"].to_string();
+    let input_path = format!("/api/core/v2/outputs/{input_id}");
+    let metadata_path = format!("/api/core/v2/outputs/{input_id}/metadata");
+
+    let url = spawn_mock_node(move |head| match head.split(' ').nth(1) {
+        Some(path) if path == input_path => (200, input_response.clone()),
+        Some(path) if path == metadata_path => (200, metadata_response.clone()),
+        _ => (404, String::new()),
+    });
+    let client = mock_node_client_builder(&url).finish().unwrap();
+
+    let mut prepared_transaction_data = client
+        .block()
+        .with_input(UtxoInput::from(input_id))
+        .unwrap()
+        .with_output(&recipient_address.to_bech32("smr"), 1_000_000)
+        .await
+        .unwrap()
+        .prepare_transaction()
+        .await
+        .unwrap();
+    assert!(client.dry_run(&prepared_transaction_data).await.unwrap().is_valid());
+
+    // Native tokens that aren't consumed by the transaction can't be created without their foundry.
+    let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+    let mut outputs = essence.outputs().to_vec();
+    outputs[0] = BasicOutputBuilder::from(outputs[0].as_basic())
+        .add_native_token(NativeToken::new(TokenId::new([1; TokenId::LENGTH]), U256::from(10)).unwrap())
+        .finish_output(client.get_token_supply().await.unwrap())
+        .unwrap();
+    prepared_transaction_data.essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(essence.network_id(), *essence.inputs_commitment())
+            .with_inputs(essence.inputs().to_vec())
+            .with_outputs(outputs)
+            .finish(&client.get_protocol_parameters().await.unwrap())
+            .unwrap(),
+    );
+
+    let report = client.dry_run(&prepared_transaction_data).await.unwrap();
+    assert_eq!(report.conflict, ConflictReason::InvalidNativeTokens);
+    assert!(report.unspendable_inputs.is_empty());
+}
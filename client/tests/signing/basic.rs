@@ -354,3 +354,84 @@ async fn two_signature_unlocks() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn unlocks_with_addresses() -> Result<()> {
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic()?)?;
+
+    let address_0 = secret_manager
+        .generate_addresses(SHIMMER_COIN_TYPE, 0, 0..1, false, None)
+        .await?[0];
+    let bech32_address_0 = &address_0.to_bech32(SHIMMER_TESTNET_BECH32_HRP);
+
+    let protocol_parameters = protocol_parameters();
+
+    let input = || {
+        Basic(
+            1_000_000,
+            bech32_address_0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Chain::from_u32_hardened(vec![
+                HD_WALLET_TYPE,
+                SHIMMER_COIN_TYPE,
+                0,
+                0,
+                0,
+            ])),
+        )
+    };
+    let inputs = build_inputs(vec![input(), input()]);
+
+    let outputs = build_outputs(vec![Basic(
+        2_000_000,
+        bech32_address_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(
+            protocol_parameters.network_id(),
+            InputsCommitment::new(inputs.iter().map(|i| &i.output)),
+        )
+        .with_inputs(
+            inputs
+                .iter()
+                .map(|i| Input::Utxo(UtxoInput::from(*i.output_metadata.output_id())))
+                .collect(),
+        )
+        .with_outputs(outputs)
+        .finish(&protocol_parameters)?,
+    );
+
+    let prepared_transaction_data = PreparedTransactionData {
+        essence,
+        inputs_data: inputs,
+        remainder: None,
+    };
+
+    let unlocks = secret_manager
+        .sign_transaction_essence_verbose(&prepared_transaction_data, Some(0))
+        .await?;
+
+    assert_eq!(unlocks.len(), 2);
+    assert_eq!(unlocks[0].0.kind(), SignatureUnlock::KIND);
+    assert_eq!(unlocks[0].1, address_0);
+    match &unlocks[1].0 {
+        Unlock::Reference(r) => {
+            assert_eq!(r.index(), 0);
+        }
+        _ => panic!("Invalid unlock"),
+    }
+    assert_eq!(unlocks[1].1, address_0);
+
+    Ok(())
+}
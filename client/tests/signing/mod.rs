@@ -5,7 +5,7 @@ mod alias;
 mod basic;
 mod nft;
 
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 
 use crypto::keys::slip10::Chain;
 use iota_client::{
@@ -27,14 +27,15 @@ use iota_client::{
     },
     constants::{HD_WALLET_TYPE, SHIMMER_COIN_TYPE, SHIMMER_TESTNET_BECH32_HRP},
     secret::{SecretManage, SecretManageExt, SecretManager},
-    Result,
+    Client, Result,
 };
 use iota_types::block::output::AliasId;
 
 use crate::{
     addresses, build_inputs, build_outputs,
     Build::{Alias, Basic, Nft},
-    ALIAS_ID_1, ALIAS_ID_2, NFT_ID_1, NFT_ID_2, NFT_ID_3, NFT_ID_4,
+    ALIAS_ID_1, ALIAS_ID_2, BECH32_ADDRESS_ALIAS_1, BECH32_ADDRESS_ALIAS_2, BECH32_ADDRESS_ED25519_0,
+    BECH32_ADDRESS_ED25519_1, BECH32_ADDRESS_ED25519_2, BECH32_ADDRESS_NFT_1, NFT_ID_1, NFT_ID_2, NFT_ID_3, NFT_ID_4,
 };
 
 #[tokio::test]
@@ -457,3 +458,80 @@ async fn all_combined() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn required_unlock_addresses() -> Result<()> {
+    let client = Client::builder().with_node_sync_disabled().finish()?;
+    let alias_id_1 = AliasId::from_str(ALIAS_ID_1)?;
+    let nft_id_1 = NftId::from_str(NFT_ID_1)?;
+
+    let inputs = build_inputs(vec![
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Alias(
+            1_000_000,
+            alias_id_1,
+            0,
+            BECH32_ADDRESS_ED25519_1,
+            BECH32_ADDRESS_ED25519_2,
+            None,
+            None,
+            None,
+            None,
+        ),
+        // Unlocked by the alias input.
+        Nft(
+            1_000_000,
+            nft_id_1,
+            BECH32_ADDRESS_ALIAS_1,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        // Unlocked by the nft input.
+        Basic(1_000_000, BECH32_ADDRESS_NFT_1, None, None, None, None, None, None),
+        // The alias isn't an input.
+        Basic(1_000_000, BECH32_ADDRESS_ALIAS_2, None, None, None, None, None, None),
+    ]);
+    let alias_output = |state_index| {
+        build_outputs(vec![Alias(
+            1_000_000,
+            alias_id_1,
+            state_index,
+            BECH32_ADDRESS_ED25519_1,
+            BECH32_ADDRESS_ED25519_2,
+            None,
+            None,
+            None,
+            None,
+        )])
+    };
+
+    // A state transition requires the state controller, which unlocks the alias address.
+    assert_eq!(
+        client.required_unlock_addresses(&inputs, &alias_output(1)).await?,
+        addresses(vec![
+            BECH32_ADDRESS_ED25519_0,
+            BECH32_ADDRESS_ED25519_1,
+            BECH32_ADDRESS_ALIAS_2
+        ])
+        .into_iter()
+        .collect::<HashSet<_>>()
+    );
+    // A governance transition requires the governor, which doesn't unlock the alias address.
+    assert_eq!(
+        client.required_unlock_addresses(&inputs, &alias_output(0)).await?,
+        addresses(vec![
+            BECH32_ADDRESS_ED25519_0,
+            BECH32_ADDRESS_ED25519_2,
+            BECH32_ADDRESS_ALIAS_1,
+            BECH32_ADDRESS_ALIAS_2
+        ])
+        .into_iter()
+        .collect::<HashSet<_>>()
+    );
+
+    Ok(())
+}
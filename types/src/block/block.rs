@@ -148,7 +148,7 @@ impl Block {
         self.nonce
     }
 
-    /// Computes the identifier of the block.
+    /// Computes the identifier of the block, the BLAKE2b-256 hash of the packed block, as the node does.
     #[inline(always)]
     pub fn id(&self) -> BlockId {
         BlockId::new(Blake2b256::digest(self.pack_to_vec()).into())
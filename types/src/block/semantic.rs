@@ -7,11 +7,11 @@ use hashbrown::{HashMap, HashSet};
 use primitive_types::U256;
 
 use crate::block::{
-    address::Address,
+    address::{Address, AliasAddress, NftAddress},
     error::Error,
     output::{ChainId, FoundryId, InputsCommitment, NativeTokens, Output, OutputId, TokenId},
     payload::transaction::{RegularTransactionEssence, TransactionEssence, TransactionId},
-    unlock::Unlocks,
+    unlock::{Unlock, Unlocks},
 };
 
 /// Errors related to ledger types.
@@ -113,8 +113,8 @@ pub struct ValidationContext<'a> {
     pub essence_hash: [u8; 32],
     ///
     pub inputs_commitment: InputsCommitment,
-    ///
-    pub unlocks: &'a Unlocks,
+    /// The unlocks of the transaction, `None` for the validation of a transaction that isn't signed yet.
+    pub unlocks: Option<&'a Unlocks>,
     ///
     pub milestone_timestamp: u32,
     ///
@@ -145,6 +145,16 @@ impl<'a> ValidationContext<'a> {
         inputs: impl Iterator<Item = (&'a OutputId, &'a Output)> + Clone,
         unlocks: &'a Unlocks,
         milestone_timestamp: u32,
+    ) -> Self {
+        Self::new_with_unlocks(transaction_id, essence, inputs, Some(unlocks), milestone_timestamp)
+    }
+
+    fn new_with_unlocks(
+        transaction_id: &TransactionId,
+        essence: &'a RegularTransactionEssence,
+        inputs: impl Iterator<Item = (&'a OutputId, &'a Output)> + Clone,
+        unlocks: Option<&'a Unlocks>,
+        milestone_timestamp: u32,
     ) -> Self {
         Self {
             essence,
@@ -185,45 +195,134 @@ impl<'a> ValidationContext<'a> {
 
 ///
 pub fn semantic_validation(
-    mut context: ValidationContext<'_>,
+    context: ValidationContext<'_>,
     inputs: &[(OutputId, &Output)],
     unlocks: &Unlocks,
+) -> Result<ConflictReason, Error> {
+    validate(context, inputs, Some(unlocks))
+}
+
+/// Semantic validation of a transaction that isn't signed yet.
+///
+/// Instead of verifying unlocks, the addresses the inputs are locked to are considered unlocked, like signing them
+/// would do; everything else is validated like by [`semantic_validation()`]. As the transaction id depends on the
+/// unlocks, chains created by the transaction are identified with a placeholder id.
+pub fn semantic_validation_unsigned(
+    essence: &RegularTransactionEssence,
+    inputs: &[(OutputId, &Output)],
+    milestone_timestamp: u32,
+) -> Result<ConflictReason, Error> {
+    let context = ValidationContext::new_with_unlocks(
+        &TransactionId::null(),
+        essence,
+        inputs.iter().map(|(id, input)| (id, *input)),
+        None,
+        milestone_timestamp,
+    );
+
+    validate(context, inputs, None)
+}
+
+fn unlock_signed(
+    output_id: &OutputId,
+    consumed_output: &Output,
+    unlock: &Unlock,
+    inputs: &[(OutputId, &Output)],
+    context: &mut ValidationContext<'_>,
+) -> Result<(), ConflictReason> {
+    match consumed_output {
+        Output::Basic(output) => output.unlock(output_id, unlock, inputs, context),
+        Output::Alias(output) => output.unlock(output_id, unlock, inputs, context),
+        Output::Foundry(output) => output.unlock(output_id, unlock, inputs, context),
+        Output::Nft(output) => output.unlock(output_id, unlock, inputs, context),
+        _ => Ok(()),
+    }
+}
+
+// Unlocks a consumed output the way signing it would do: the Ed25519 address it's locked to gets unlocked, while alias
+// and NFT addresses need to be unlocked already by consuming the chain in a previous input.
+fn unlock_unsigned(
+    output_id: &OutputId,
+    consumed_output: &Output,
+    context: &mut ValidationContext<'_>,
+) -> Result<(), ConflictReason> {
+    let unlock_address = |address: &Address, context: &mut ValidationContext<'_>| match address {
+        Address::Ed25519(_) => {
+            context.unlocked_addresses.insert(*address);
+            Ok(())
+        }
+        _ if context.unlocked_addresses.contains(address) => Ok(()),
+        _ => Err(ConflictReason::InvalidUnlock),
+    };
+
+    match consumed_output {
+        Output::Basic(output) => unlock_address(
+            output
+                .unlock_conditions()
+                .locked_address(output.address(), context.milestone_timestamp),
+            context,
+        ),
+        Output::Alias(output) => {
+            let alias_id = output.alias_id_non_null(output_id);
+
+            match context.output_chains.get(&ChainId::from(alias_id)) {
+                Some(Output::Alias(next_state)) if output.state_index() != next_state.state_index() => {
+                    unlock_address(output.state_controller_address(), context)?;
+                    context
+                        .unlocked_addresses
+                        .insert(Address::from(AliasAddress::from(alias_id)));
+                    Ok(())
+                }
+                _ => unlock_address(output.governor_address(), context),
+            }
+        }
+        Output::Foundry(output) => unlock_address(&Address::from(*output.alias_address()), context),
+        Output::Nft(output) => {
+            unlock_address(
+                output
+                    .unlock_conditions()
+                    .locked_address(output.address(), context.milestone_timestamp),
+                context,
+            )?;
+            context
+                .unlocked_addresses
+                .insert(Address::from(NftAddress::from(output.nft_id_non_null(output_id))));
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate(
+    mut context: ValidationContext<'_>,
+    inputs: &[(OutputId, &Output)],
+    unlocks: Option<&Unlocks>,
 ) -> Result<ConflictReason, Error> {
     // Validation of the inputs commitment.
     if context.essence.inputs_commitment() != &context.inputs_commitment {
         return Ok(ConflictReason::InputsCommitmentsMismatch);
     }
 
+    let mut unlocks = unlocks.map(|unlocks| unlocks.iter());
+
     // Validation of inputs.
-    for ((output_id, consumed_output), unlock) in inputs.iter().zip(unlocks.iter()) {
-        let (conflict, amount, consumed_native_tokens, unlock_conditions) = match consumed_output {
-            Output::Basic(output) => (
-                output.unlock(output_id, unlock, inputs, &mut context),
-                output.amount(),
-                output.native_tokens(),
-                output.unlock_conditions(),
-            ),
-            Output::Alias(output) => (
-                output.unlock(output_id, unlock, inputs, &mut context),
-                output.amount(),
-                output.native_tokens(),
-                output.unlock_conditions(),
-            ),
-            Output::Foundry(output) => (
-                output.unlock(output_id, unlock, inputs, &mut context),
-                output.amount(),
-                output.native_tokens(),
-                output.unlock_conditions(),
-            ),
-            Output::Nft(output) => (
-                output.unlock(output_id, unlock, inputs, &mut context),
-                output.amount(),
-                output.native_tokens(),
-                output.unlock_conditions(),
-            ),
+    for (output_id, consumed_output) in inputs {
+        let (amount, consumed_native_tokens, unlock_conditions) = match consumed_output {
+            Output::Basic(output) => (output.amount(), output.native_tokens(), output.unlock_conditions()),
+            Output::Alias(output) => (output.amount(), output.native_tokens(), output.unlock_conditions()),
+            Output::Foundry(output) => (output.amount(), output.native_tokens(), output.unlock_conditions()),
+            Output::Nft(output) => (output.amount(), output.native_tokens(), output.unlock_conditions()),
             _ => return Err(Error::UnsupportedOutputKind(consumed_output.kind())),
         };
 
+        let conflict = match unlocks.as_mut() {
+            Some(unlocks) => match unlocks.next() {
+                Some(unlock) => unlock_signed(output_id, consumed_output, unlock, inputs, &mut context),
+                None => break,
+            },
+            None => unlock_unsigned(output_id, consumed_output, &mut context),
+        };
+
         if let Err(conflict) = conflict {
             return Ok(conflict);
         }
@@ -12,7 +12,7 @@ use iota_types::block::{
         parents::rand_parents,
         payload::{rand_tagged_data_payload, rand_treasury_transaction_payload},
     },
-    Block, BlockBuilder, Error,
+    Block, BlockBuilder, BlockId, Error,
 };
 use packable::{error::UnpackError, PackableExt};
 
@@ -126,3 +126,16 @@ fn getters() {
     assert_eq!(*block.payload().as_ref().unwrap(), &payload);
     assert_eq!(block.nonce(), nonce);
 }
+
+#[test]
+fn id() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::null()]).unwrap())
+        .with_nonce(42)
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        block.id().to_string(),
+        "0x3a4d6cab5376ed973533e66ba8415d9324c460268c13fa938e6fced0646f4cbd"
+    );
+}